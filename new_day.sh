@@ -3,6 +3,67 @@
 set -euo pipefail
 
 DAY_NUM="$1"
+DAY="day$DAY_NUM"
 
-touch input/day$DAY_NUM.txt src/bin/day$DAY_NUM.rs src/bin/examples/day$DAY_NUM.txt
-code input/day$DAY_NUM.txt src/bin/day$DAY_NUM.rs src/bin/examples/day$DAY_NUM.txt
\ No newline at end of file
+touch input/$DAY.txt src/days/examples/$DAY.txt
+
+# Starting points that build, left alone if the day has already been started
+if [ ! -s src/days/$DAY.rs ]; then
+    cat > src/days/$DAY.rs <<EOF
+use crate::aoc::{Result, Solution};
+
+pub struct Day$DAY_NUM;
+
+impl Solution for Day$DAY_NUM {
+    const DAY: u8 = $DAY_NUM;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(_input: &Self::Input) -> Result<Self::Answer1> {
+        todo!()
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Self::Answer2> {
+        todo!()
+    }
+}
+EOF
+fi
+
+if [ ! -s src/bin/$DAY.rs ]; then
+    cat > src/bin/$DAY.rs <<EOF
+use aoc2023::{aoc, days::$DAY::Day$DAY_NUM};
+
+fn main() {
+    aoc::cli::parse().run(&Day$DAY_NUM);
+}
+EOF
+fi
+
+# Register the day with the runner, after the days before it
+if ! grep -q "^pub mod $DAY;" src/days.rs; then
+    awk -v day="$DAY" -v num="$DAY_NUM" '
+        { lines[NR] = $0 }
+        /^pub mod day[0-9]+;$/ { last_mod = NR }
+        /^pub static ALL/ { in_all = 1 }
+        in_all && /^\];$/ { all_end = NR; in_all = 0 }
+        END {
+            for (i = 1; i <= NR; i++) {
+                if (i == all_end) print "    &" day "::Day" num ","
+                print lines[i]
+                if (i == last_mod) print "pub mod " day ";"
+            }
+        }
+    ' src/days.rs > src/days.rs.new
+    mv src/days.rs.new src/days.rs
+fi
+
+if command -v code > /dev/null; then
+    code input/$DAY.txt src/days/$DAY.rs src/days/examples/$DAY.txt
+fi
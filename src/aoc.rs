@@ -1,3 +1,10 @@
+pub mod answers;
+pub mod bench;
 pub mod cli;
+pub mod error;
 pub mod example;
 pub mod parse;
+pub mod solution;
+
+pub use error::{Error, Result};
+pub use solution::{DynSolution, Part, Solution};
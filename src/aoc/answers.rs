@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    path::Path,
+};
+
+use colored::Colorize;
+
+use super::{
+    error::{read_input, Error, Result},
+    parse::{parse_setting, Setting, Value},
+    Part,
+};
+
+/// Known good answers, loaded from a small `key = value` file with a
+/// section per day:
+///
+/// ```text
+/// [day1]
+/// part1 = 142
+/// part2 = "281"
+/// ```
+#[derive(Debug, Default)]
+pub struct KnownAnswers(HashMap<(u8, Part), String>);
+
+/// How a computed answer compares to the known one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "{}", "pass".green().bold()),
+            Verdict::Fail { expected } => {
+                write!(f, "{} (expected {expected})", "fail".red().bold())
+            }
+            Verdict::Unknown => write!(f, "{}", "unknown".yellow()),
+        }
+    }
+}
+
+impl KnownAnswers {
+    /// Load answers from `path`. A missing file just means nothing is known.
+    pub fn load(path: &Path) -> Result<Self> {
+        match read_input(path) {
+            Ok(s) => Self::parse(&s),
+            Err(Error::MissingInput(_)) => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut answers = HashMap::new();
+        let mut day = None;
+
+        for line in input.lines() {
+            let (key, value) = match parse_setting(input, line)? {
+                None => continue,
+                Some(Setting::Section(name)) => {
+                    let number = name
+                        .strip_prefix("day")
+                        .ok_or_else(|| Error::parse(input, name, "expected `[dayN]`"))?;
+                    day = Some(number.parse().map_err(|_| {
+                        Error::parse(input, number, format!("invalid day `{number}`"))
+                    })?);
+                    continue;
+                }
+                Some(Setting::Entry(key, value)) => (key, value),
+            };
+
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(Error::parse(input, key, "expected `part1` or `part2`")),
+            };
+            let day = day.ok_or_else(|| Error::parse(input, key, "answer outside a `[dayN]`"))?;
+
+            let value = match value {
+                Value::Quoted(value) => value,
+                Value::Bare(value) if value.bytes().all(|c| c.is_ascii_digit() || c == b'-') => {
+                    value
+                }
+                Value::Bare(value) => {
+                    return Err(Error::parse(input, value, "expected a number or string"))
+                }
+            };
+
+            answers.insert((day, part), value.to_owned());
+        }
+
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check() {
+        let answers = KnownAnswers::parse(
+            "# examples\n[day1]\npart1 = 142\npart2 = \"281\" # words too\n\n[day 2]\n",
+        );
+        assert!(answers.is_err());
+
+        let answers = KnownAnswers::parse(
+            "# examples\n[day1]\npart1 = 142\npart2 = \"281\" # words too\n\n[day9]\npart2 = -2\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.check(1, Part::One, "142"), Verdict::Pass);
+        assert_eq!(answers.check(1, Part::Two, "281"), Verdict::Pass);
+        assert_eq!(
+            answers.check(1, Part::One, "143"),
+            Verdict::Fail {
+                expected: "142".to_owned()
+            }
+        );
+        assert_eq!(answers.check(9, Part::Two, "-2"), Verdict::Pass);
+        assert_eq!(answers.check(9, Part::One, "114"), Verdict::Unknown);
+
+        let answers = KnownAnswers::parse("[day3]\npart1 = \"a#b\" # hash\n").unwrap();
+        assert_eq!(answers.check(3, Part::One, "a#b"), Verdict::Pass);
+    }
+}
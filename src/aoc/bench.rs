@@ -0,0 +1,188 @@
+use std::{
+    fmt::{self, Display, Write},
+    time::{Duration, Instant},
+};
+
+use super::{Part, Result};
+
+/// A stage of running a day's solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => f.write_str("Parse"),
+            Stage::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// Summary of the wall time taken over repeated runs of a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        samples.sort();
+
+        let runs = samples.len();
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / runs as u32,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.runs == 1 {
+            write!(f, "{:.1?}", self.min)
+        } else {
+            write!(
+                f,
+                "min {:.1?}, median {:.1?}, mean {:.1?} ({} runs)",
+                self.min, self.median, self.mean, self.runs
+            )
+        }
+    }
+}
+
+/// Run `f` `runs` times (at least once), returning the last result and the
+/// time taken.
+pub fn time_stage<T>(runs: usize, mut f: impl FnMut() -> Result<T>) -> Result<(T, Stats)> {
+    let mut samples = Vec::with_capacity(runs.max(1));
+    let mut out = None;
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        out = Some(result);
+    }
+
+    Ok((out.unwrap(), Stats::from_samples(samples)))
+}
+
+/// How long each stage of one day took, `None` for parts that weren't run.
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+impl Timings {
+    pub fn iter(&self) -> impl Iterator<Item = (Stage, &Stats)> {
+        [
+            (Stage::Parse, Some(&self.parse)),
+            (Stage::Part(Part::One), self.part1.as_ref()),
+            (Stage::Part(Part::Two), self.part2.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(stage, stats)| Some((stage, stats?)))
+    }
+}
+
+/// Every day's timings as a JSON array with one object per stage, durations
+/// in nanoseconds.
+pub fn to_json(days: &[(u8, Timings)]) -> String {
+    let rows: Vec<_> = days
+        .iter()
+        .flat_map(|(day, timings)| {
+            timings.iter().map(move |(stage, stats)| {
+                format!(
+                    r#"  {{"day": {day}, "stage": "{stage}", "runs": {}, "min_ns": {}, "median_ns": {}, "mean_ns": {}}}"#,
+                    stats.runs,
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.mean.as_nanos()
+                )
+            })
+        })
+        .collect();
+
+    format!("[\n{}\n]", rows.join(",\n"))
+}
+
+/// Every day's timings as a Markdown table, one row per day using the
+/// median time of each stage.
+pub fn to_markdown(days: &[(u8, Timings)]) -> String {
+    let cell =
+        |stats: Option<&Stats>| stats.map_or("-".to_owned(), |s| format!("{:.1?}", s.median));
+
+    let mut out = String::from("| Day | Parse | Part 1 | Part 2 | Total |\n");
+    out += "|----:|------:|-------:|-------:|------:|\n";
+
+    let mut total = Duration::ZERO;
+    for (day, timings) in days {
+        let day_total: Duration = timings.iter().map(|(_, stats)| stats.median).sum();
+        total += day_total;
+
+        writeln!(
+            out,
+            "| {day} | {} | {} | {} | {day_total:.1?} |",
+            cell(Some(&timings.parse)),
+            cell(timings.part1.as_ref()),
+            cell(timings.part2.as_ref()),
+        )
+        .unwrap();
+    }
+    writeln!(out, "| **Total** | | | | **{total:.1?}** |").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!((stats.min, stats.median, stats.mean), (ms(1), ms(3), ms(3)));
+
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!((stats.min, stats.median, stats.mean), (ms(1), ms(3), ms(4)));
+    }
+
+    #[test]
+    fn test_reports() {
+        let stats = Stats::from_samples(vec![ms(2)]);
+        let days = [(
+            3,
+            Timings {
+                parse: stats,
+                part1: Some(stats),
+                part2: None,
+            },
+        )];
+
+        assert_eq!(
+            to_json(&days),
+            "[\n  {\"day\": 3, \"stage\": \"Parse\", \"runs\": 1, \"min_ns\": 2000000, \"median_ns\": 2000000, \"mean_ns\": 2000000},\n  {\"day\": 3, \"stage\": \"Part 1\", \"runs\": 1, \"min_ns\": 2000000, \"median_ns\": 2000000, \"mean_ns\": 2000000}\n]"
+        );
+        assert!(to_markdown(&days).contains("| 3 | 2.0ms | 2.0ms | - | 4.0ms |"));
+    }
+}
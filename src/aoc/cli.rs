@@ -1,43 +1,132 @@
 use std::{
     env,
-    fs::{read_to_string, File},
-    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
 };
 
 use clap::Parser;
 
+use super::{error::read_input, DynSolution, Error, Result, Solution};
+
+/// Where puzzle inputs live when no input file is given.
+#[derive(clap::Args, Debug, Clone)]
+pub struct InputDir {
+    /// Directory holding a `dayN.txt` input for each day
+    #[arg(
+        id = "input_dir",
+        long = "input-dir",
+        env = "AOC_INPUT_DIR",
+        default_value = "input"
+    )]
+    pub path: PathBuf,
+}
+
+impl InputDir {
+    pub fn day_file(&self, day: u8) -> PathBuf {
+        self.path.join(format!("day{day}.txt"))
+    }
+}
+
 #[derive(Parser, Debug)]
 pub struct Cli {
-    pub input: Option<std::path::PathBuf>,
+    /// Input file, or `-` to read from stdin
+    pub input: Option<PathBuf>,
+
+    /// Day whose input to read, instead of the one named by the binary
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    #[command(flatten)]
+    pub input_dir: InputDir,
 }
 
 impl Cli {
-    pub fn line_reader(&self) -> impl Iterator<Item = String> + '_ {
-        let f = File::open(&self.input_file()).unwrap();
+    pub fn input_string(&self) -> Result<String> {
+        read_input(&self.input_file()?)
+    }
+
+    /// The day being run, from `--day` or else the name of the binary.
+    pub fn day(&self) -> Option<u8> {
+        self.day.or_else(exe_day)
+    }
 
-        BufReader::new(f).lines().map(|l| l.unwrap())
+    pub fn input_file(&self) -> Result<PathBuf> {
+        match (&self.input, self.day()) {
+            (Some(f), _) => Ok(f.clone()),
+            (None, Some(day)) => Ok(self.input_dir.day_file(day)),
+            (None, None) => Err(Error::UnknownDay),
+        }
     }
 
-    pub fn input_string(&self) -> String {
-        read_to_string(&self.input_file()).unwrap()
+    /// Read and parse the input for `S`, for binaries that want to do more
+    /// with it than print the answers.
+    pub fn parse_input<S: Solution>(&self) -> Result<S::Input> {
+        S::parse(&self.input_string()?)
     }
 
-    pub fn input_file(&self) -> std::path::PathBuf {
-        if let Some(f) = &self.input {
-            f.clone()
-        } else {
-            let day_name = env::args()
-                .last()
-                .unwrap()
-                .rsplit_once("/")
-                .unwrap()
-                .1
-                .to_owned();
-            format!("input/{}.txt", day_name).into()
+    pub fn run(&self, solution: &dyn DynSolution) {
+        match self
+            .input_string()
+            .and_then(|input| solution.run(&input, None))
+        {
+            Ok(answers) => {
+                println!("Part 1: {}", answers.part1.unwrap());
+                println!("Part 2: {}", answers.part2.unwrap());
+            }
+            Err(e) => e.exit(),
         }
     }
 }
 
+/// The day in a binary name like `day5`, ignoring any suffix after the number.
+fn day_from_name(name: &str) -> Option<u8> {
+    let number = name.strip_prefix("day")?;
+    let end = number
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(number.len());
+
+    number[..end].parse().ok()
+}
+
+fn day_from_path(path: &Path) -> Option<u8> {
+    day_from_name(path.file_stem()?.to_str()?)
+}
+
+/// Work out the day from the name the binary was run as, falling back to the
+/// file it resolves to in case it was run through a differently named link.
+fn exe_day() -> Option<u8> {
+    env::args_os()
+        .next()
+        .and_then(|arg0| day_from_path(arg0.as_ref()))
+        .or_else(|| day_from_path(&env::current_exe().ok()?))
+}
+
 pub fn parse() -> Cli {
     Cli::parse()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_day_from_name() {
+        assert_eq!(day_from_name("day5"), Some(5));
+        assert_eq!(day_from_name("day12"), Some(12));
+        assert_eq!(day_from_name("day8-0123abcd"), Some(8));
+        assert_eq!(day_from_name("aoc"), None);
+        assert_eq!(day_from_name("day"), None);
+        assert_eq!(day_from_path("target/release/day3.exe".as_ref()), Some(3));
+    }
+
+    #[test]
+    fn test_input_file() {
+        let cli = Cli::parse_from(["aoc", "--day", "4", "--input-dir", "elsewhere"]);
+        assert_eq!(
+            cli.input_file().unwrap(),
+            PathBuf::from("elsewhere/day4.txt")
+        );
+
+        let cli = Cli::parse_from(["day7", "mine.txt"]);
+        assert_eq!(cli.input_file().unwrap(), PathBuf::from("mine.txt"));
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Anything that can go wrong between finding a puzzle input and answering it.
+#[derive(Debug)]
+pub enum Error {
+    /// No input file was given and the day couldn't be worked out.
+    UnknownDay,
+    /// The input file doesn't exist.
+    MissingInput(PathBuf),
+    /// The input file exists but couldn't be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The input was read but isn't in the expected format.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// The input parsed fine but has no answer.
+    Unsolvable(String),
+}
+
+impl Error {
+    /// A parse error at `at`, which should be a slice of `input`. The line and
+    /// column are worked out from where `at` sits inside `input`, and point
+    /// at the end of `input` if it isn't part of it.
+    pub fn parse(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Error::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..line_end].to_owned(),
+            message: message.into(),
+        }
+    }
+
+    /// Shift the line of a parse error down by `lines`, for errors raised
+    /// while parsing one line of a larger input.
+    pub fn offset_lines(self, lines: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                text,
+                message,
+            } => Error::Parse {
+                line: line + lines,
+                column,
+                text,
+                message,
+            },
+            e => e,
+        }
+    }
+
+    /// A multi-line, coloured rendering for showing to a person.
+    pub fn render(&self) -> String {
+        let mut out = format!("{}: {}", "error".red().bold(), self.to_string().bold());
+
+        if let Error::Parse {
+            line, column, text, ..
+        } = self
+        {
+            let gutter = " ".repeat(line.to_string().len());
+            let bar = "|".blue().bold();
+            out += &format!(
+                "\n{gutter}{} line {line}, column {column}\n{gutter} {bar}\n{} {bar} {text}\n{gutter} {bar} {}{}",
+                "-->".blue().bold(),
+                line.to_string().blue().bold(),
+                " ".repeat(column - 1),
+                "^".red().bold(),
+            );
+        }
+
+        out
+    }
+
+    /// Report the error on stderr and exit with a failure status.
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self.render());
+        std::process::exit(1)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownDay => f.write_str("couldn't tell which day to run, pass --day"),
+            Error::MissingInput(path) => write!(f, "no input file at {}", path.display()),
+            Error::Io { path, source } => write!(f, "couldn't read {}: {source}", path.display()),
+            Error::Parse { message, .. } => f.write_str(message),
+            Error::Unsolvable(reason) => write!(f, "input has no solution: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Read a whole input file, or stdin if the path is `-`, telling a missing
+/// file apart from other failures.
+pub fn read_input(path: &Path) -> Result<String> {
+    let read = if path == Path::new("-") {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(path)
+    };
+
+    read.map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => Error::MissingInput(path.to_owned()),
+        _ => Error::Io {
+            path: path.to_owned(),
+            source,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_position() {
+        let input = "first line\nsecond line\nthird";
+
+        let Error::Parse {
+            line, column, text, ..
+        } = Error::parse(input, &input[18..], "bad")
+        else {
+            panic!("not a parse error");
+        };
+        assert_eq!((line, column, text.as_str()), (2, 8, "second line"));
+
+        let Error::Parse {
+            line, column, text, ..
+        } = Error::parse(input, "elsewhere", "bad")
+        else {
+            panic!("not a parse error");
+        };
+        assert_eq!((line, column, text.as_str()), (3, 6, "third"));
+    }
+}
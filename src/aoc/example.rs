@@ -1,7 +1,4 @@
-use std::{
-    fs::{read_to_string, File},
-    io::{BufRead, BufReader},
-};
+use super::{error::read_input, Result};
 
 fn example_path(example: &str) -> std::path::PathBuf {
     std::path::Path::new(file!())
@@ -9,15 +6,9 @@ fn example_path(example: &str) -> std::path::PathBuf {
         .unwrap()
         .parent()
         .unwrap()
-        .join(format!("bin/examples/{example}"))
+        .join(format!("days/examples/{example}"))
 }
 
-pub fn example_lines(example: &str) -> impl Iterator<Item = String> {
-    BufReader::new(File::open(example_path(example)).unwrap())
-        .lines()
-        .map(|l| l.unwrap())
-}
-
-pub fn example_string(example: &str) -> String {
-    read_to_string(example_path(example)).unwrap()
+pub fn example_string(example: &str) -> Result<String> {
+    read_input(&example_path(example))
 }
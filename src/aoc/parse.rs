@@ -1,5 +1,7 @@
 use nom;
 
+use super::error::{Error, Result};
+
 pub fn parse_number<T>(s: &str) -> nom::IResult<&str, T>
 where
     T: std::str::FromStr,
@@ -9,3 +11,134 @@ where
         |s: &str| s.parse::<T>(),
     )(s)
 }
+
+/// Parse `s` as a `T`, reporting failure as a parse error at `s` in `input`.
+pub fn parse_num<T>(input: &str, s: &str) -> Result<T>
+where
+    T: std::str::FromStr,
+{
+    s.parse()
+        .map_err(|_| Error::parse(input, s, format!("invalid number `{s}`")))
+}
+
+/// Parse every line of `input` with `f`, which reports errors relative to the
+/// line it was given.
+pub fn parse_lines<T>(input: &str, mut f: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.offset_lines(i)))
+        .collect()
+}
+
+/// Turn a failed nom parse of `input` into a parse error.
+pub fn nom_error(input: &str, e: nom::Err<nom::error::Error<&str>>) -> Error {
+    match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => Error::parse(
+            input,
+            e.input,
+            format!("unexpected input ({})", e.code.description()),
+        ),
+        nom::Err::Incomplete(_) => {
+            Error::parse(input, &input[input.len()..], "unexpected end of input")
+        }
+    }
+}
+
+/// A line of the small `key = value` files used for settings and answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting<'a> {
+    /// A `[name]` header starting a new section.
+    Section(&'a str),
+    Entry(&'a str, Value<'a>),
+}
+
+/// The value of a [`Setting::Entry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value<'a> {
+    /// The text between double quotes, which can't contain a quote itself.
+    Quoted(&'a str),
+    /// Anything else, such as a number.
+    Bare(&'a str),
+}
+
+/// Parse one `line` of `input` as a [`Setting`], or `None` if it's blank. A
+/// `#` starts a comment unless it's inside a quoted value.
+pub fn parse_setting<'a>(input: &str, line: &'a str) -> Result<Option<Setting<'a>>> {
+    let mut quoted = false;
+    let end = line
+        .find(|c| {
+            quoted ^= c == '"';
+            c == '#' && !quoted
+        })
+        .unwrap_or(line.len());
+    let line = line[..end].trim();
+
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(header) = line.strip_prefix('[') {
+        let name = header
+            .strip_suffix(']')
+            .ok_or_else(|| Error::parse(input, line, "expected `[name]`"))?;
+        return Ok(Some(Setting::Section(name.trim())));
+    }
+
+    let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| Error::parse(input, line, "expected `key = value`"))?;
+    let value = value.trim();
+
+    let value = match value.strip_prefix('"') {
+        Some(rest) => {
+            let close = rest
+                .find('"')
+                .ok_or_else(|| Error::parse(input, value, "unterminated string"))?;
+            if close + 1 < rest.len() {
+                return Err(Error::parse(
+                    input,
+                    &rest[close + 1..],
+                    "unexpected text after string",
+                ));
+            }
+            Value::Quoted(&rest[..close])
+        }
+        None if value.is_empty() => return Err(Error::parse(input, value, "expected a value")),
+        None => Value::Bare(value),
+    };
+
+    Ok(Some(Setting::Entry(key.trim(), value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_setting() {
+        let setting = |line| parse_setting(line, line);
+        assert_eq!(setting("  # just a comment").unwrap(), None);
+        assert_eq!(setting("").unwrap(), None);
+        assert_eq!(
+            setting("[day 3] # third").unwrap(),
+            Some(Setting::Section("day 3"))
+        );
+        assert_eq!(
+            setting("part1 = 142 # known").unwrap(),
+            Some(Setting::Entry("part1", Value::Bare("142")))
+        );
+        assert_eq!(
+            setting("part1 = \"a#b\" # the # is kept").unwrap(),
+            Some(Setting::Entry("part1", Value::Quoted("a#b")))
+        );
+        assert_eq!(
+            setting("empty=\"\"").unwrap(),
+            Some(Setting::Entry("empty", Value::Quoted("")))
+        );
+
+        for bad in ["[day1", "part1", "part1 =", "part1 = \"281", "a = \"b\"c"] {
+            assert!(setting(bad).is_err(), "{bad}");
+        }
+    }
+}
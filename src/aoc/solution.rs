@@ -0,0 +1,158 @@
+use std::fmt::Display;
+
+use super::{
+    bench::{time_stage, Timings},
+    error::Result,
+};
+
+/// One of the two halves of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+/// A single day's puzzle, split into the stages every day shares: parse the
+/// input once, then answer each part from the parsed form.
+pub trait Solution {
+    /// Day of the month this solution is for.
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// The answers produced by a run, `None` for any part that wasn't asked for.
+#[derive(Debug, Default)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// Every answer that was produced, in part order.
+    pub fn iter(&self) -> impl Iterator<Item = (Part, &str)> {
+        [(Part::One, &self.part1), (Part::Two, &self.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| Some((part, answer.as_deref()?)))
+    }
+}
+
+/// Object safe view of a [`Solution`], so that every day can live in one
+/// list and be driven without knowing its input type.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    /// Parse `input` and answer the requested parts, repeating every stage
+    /// `runs` times to time it.
+    fn bench(&self, input: &str, part: Option<Part>, runs: usize) -> Result<(Answers, Timings)>;
+
+    fn run(&self, input: &str, part: Option<Part>) -> Result<Answers> {
+        self.bench(input, part, 1).map(|(answers, _)| answers)
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn bench(&self, input: &str, part: Option<Part>, runs: usize) -> Result<(Answers, Timings)> {
+        let (input, parse) = time_stage(runs, || S::parse(input))?;
+        let mut answers = Answers::default();
+        let mut timings = Timings {
+            parse,
+            part1: None,
+            part2: None,
+        };
+
+        if part != Some(Part::Two) {
+            let (answer, stats) = time_stage(runs, || S::part1(&input))?;
+            answers.part1 = Some(answer.to_string());
+            timings.part1 = Some(stats);
+        }
+        if part != Some(Part::One) {
+            let (answer, stats) = time_stage(runs, || S::part2(&input))?;
+            answers.part2 = Some(answer.to_string());
+            timings.part2 = Some(stats);
+        }
+
+        Ok((answers, timings))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aoc::{parse::parse_num, Error};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+
+        type Input = Vec<usize>;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            input
+                .split_ascii_whitespace()
+                .map(|n| parse_num(input, n))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+            input
+                .iter()
+                .try_fold(1usize, |acc, n| acc.checked_mul(*n))
+                .ok_or_else(|| Error::Unsolvable("product overflows".to_owned()))
+        }
+    }
+
+    #[test]
+    fn test_run_parts() {
+        let answers = Sum.run("2 3 4", None).unwrap();
+        assert_eq!(answers.part1.as_deref(), Some("9"));
+        assert_eq!(answers.part2.as_deref(), Some("24"));
+
+        let answers = Sum.run("2 3 4", Some(Part::Two)).unwrap();
+        assert_eq!(answers.part1, None);
+        assert_eq!(answers.part2.as_deref(), Some("24"));
+
+        assert!(matches!(
+            Sum.run("2 x", None),
+            Err(Error::Parse {
+                line: 1,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Sum.run(&format!("2 {}", usize::MAX), Some(Part::Two)),
+            Err(Error::Unsolvable(_))
+        ));
+    }
+}
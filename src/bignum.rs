@@ -0,0 +1,451 @@
+//! Arbitrary precision unsigned integers, with just the arithmetic the
+//! closed form solutions need once their inputs stop fitting in a `u128`.
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    ops::{Add, Mul, Shl, Shr, Sub},
+    str::FromStr,
+};
+
+/// An unsigned integer of any size.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    // Little endian base 2^32 digits, without trailing zeros, so zero is empty
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(1u8)
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// How many bits are needed to write the number down, zero for zero.
+    pub fn bits(&self) -> u32 {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() as u32 * 32 - top.leading_zeros(),
+            None => 0,
+        }
+    }
+
+    /// The value as a `u128`, if it fits.
+    pub fn to_u128(&self) -> Option<u128> {
+        (self.limbs.len() <= 4).then(|| {
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0, |acc, &limb| (acc << 32) | limb as u128)
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if self < other {
+            return None;
+        }
+
+        let mut borrow = false;
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let (diff, b1) = limb.overflowing_sub(other.limbs.get(i).copied().unwrap_or(0));
+            let (diff, b2) = diff.overflowing_sub(borrow as u32);
+            limbs.push(diff);
+            borrow = b1 || b2;
+        }
+
+        Some(Self::from_limbs(limbs))
+    }
+
+    /// `self * m + a`, the step when reading digits in.
+    pub fn mul_add_small(&self, m: u32, a: u32) -> Self {
+        let mut carry = a as u64;
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        for &limb in &self.limbs {
+            let wide = limb as u64 * m as u64 + carry;
+            limbs.push(wide as u32);
+            carry = wide >> 32;
+        }
+        limbs.push(carry as u32);
+
+        Self::from_limbs(limbs)
+    }
+
+    /// The quotient and remainder on dividing by a non-zero `d`, the step
+    /// when writing digits out.
+    pub fn div_rem_small(&self, d: u32) -> (Self, u32) {
+        let mut rem = 0u64;
+        let mut limbs = vec![0; self.limbs.len()];
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let wide = (rem << 32) | limb as u64;
+            limbs[i] = (wide / d as u64) as u32;
+            rem = wide % d as u64;
+        }
+
+        (Self::from_limbs(limbs), rem as u32)
+    }
+
+    /// The quotient and remainder on dividing by a non-zero `d`, by binary
+    /// long division.
+    pub fn div_rem(&self, d: &Self) -> (Self, Self) {
+        assert!(!d.is_zero(), "attempt to divide by zero");
+
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut rem = Self::zero();
+        for bit in (0..self.bits()).rev() {
+            let (limb, offset) = ((bit / 32) as usize, bit % 32);
+            rem = (&rem << 1).mul_add_small(1, (self.limbs[limb] >> offset) & 1);
+            if let Some(smaller) = rem.checked_sub(d) {
+                rem = smaller;
+                quotient[limb] |= 1 << offset;
+            }
+        }
+
+        (Self::from_limbs(quotient), rem)
+    }
+
+    /// The integer square root, the largest `r` with `r * r <= self`, found
+    /// a bit at a time so that it never needs long division.
+    pub fn isqrt(&self) -> Self {
+        let Some(top) = self.bits().checked_sub(1) else {
+            return Self::zero();
+        };
+
+        let mut rem = self.clone();
+        let mut root = Self::zero();
+        // The largest power of four that isn't bigger than self
+        let mut bit = Self::one() << (top & !1);
+
+        while !bit.is_zero() {
+            let trial = &root + &bit;
+            root = &root >> 1;
+            if let Some(smaller) = rem.checked_sub(&trial) {
+                rem = smaller;
+                root = &root + &bit;
+            }
+            bit = &bit >> 2;
+        }
+
+        root
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigUint {
+                fn from(n: $t) -> Self {
+                    let n = n as u128;
+                    Self::from_limbs((0..4).map(|i| (n >> (32 * i)) as u32).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(u8, u16, u32, u64, u128, usize);
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = match self.limbs.len() >= other.limbs.len() {
+            true => (self, other),
+            false => (other, self),
+        };
+
+        let mut carry = 0u64;
+        let mut limbs = Vec::with_capacity(long.limbs.len() + 1);
+        for (i, &limb) in long.limbs.iter().enumerate() {
+            let wide = limb as u64 + short.limbs.get(i).copied().unwrap_or(0) as u64 + carry;
+            limbs.push(wide as u32);
+            carry = wide >> 32;
+        }
+        limbs.push(carry as u32);
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    /// Panics if `other` is bigger, like unsigned machine integers.
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let wide = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = wide as u32;
+                carry = wide >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+macro_rules! forward_owned {
+    ($($trait:ident $method:ident),*) => {
+        $(
+            impl $trait for BigUint {
+                type Output = BigUint;
+
+                fn $method(self, other: BigUint) -> BigUint {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned!(Add add, Sub sub, Mul mul);
+
+impl Shl<u32> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: u32) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+
+        let (whole, bits) = ((shift / 32) as usize, shift % 32);
+        let mut limbs = vec![0; whole];
+        let mut carry = 0;
+        for &limb in &self.limbs {
+            limbs.push((limb << bits) | carry);
+            // Widened first, as a u32 can't be shifted by the full 32 bits
+            carry = ((limb as u64) >> (32 - bits)) as u32;
+        }
+        limbs.push(carry);
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shl<u32> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: u32) -> BigUint {
+        &self << shift
+    }
+}
+
+impl Shr<u32> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: u32) -> BigUint {
+        let (whole, bits) = ((shift / 32) as usize, shift % 32);
+        let limbs = self.limbs.get(whole..).unwrap_or_default();
+
+        let shifted = limbs
+            .iter()
+            .enumerate()
+            .map(|(i, &limb)| {
+                let above = limbs.get(i + 1).copied().unwrap_or(0) as u64;
+                (((above << 32) | limb as u64) >> bits) as u32
+            })
+            .collect();
+
+        BigUint::from_limbs(shifted)
+    }
+}
+
+impl Shr<u32> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: u32) -> BigUint {
+        &self >> shift
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        // Nine decimal digits at a time, least significant first
+        let mut chunks = vec![];
+        let mut n = self.clone();
+        loop {
+            let (q, r) = n.div_rem_small(CHUNK);
+            chunks.push(r);
+            n = q;
+            if n.is_zero() {
+                break;
+            }
+        }
+
+        let mut chunks = chunks.iter().rev();
+        let mut s = chunks.next().unwrap().to_string();
+        for chunk in chunks {
+            s += &format!("{chunk:09}");
+        }
+        f.pad_integral(true, "", &s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a non-empty string of decimal digits")
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBigUintError);
+        }
+
+        s.chars().try_fold(Self::zero(), |n, c| {
+            let digit = c.to_digit(10).ok_or(ParseBigUintError)?;
+            Ok(n.mul_add_small(10, digit))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "0",
+            "7",
+            "4294967296",
+            "1000000000",
+            "123456789012345678901234567890",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(format!("{:>5}", big("42")), "   42");
+        assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(BigUint::from(0u8), BigUint::zero());
+
+        assert_eq!("".parse::<BigUint>(), Err(ParseBigUintError));
+        assert_eq!("12a".parse::<BigUint>(), Err(ParseBigUintError));
+        assert_eq!("-1".parse::<BigUint>(), Err(ParseBigUintError));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // Checked against u128 where it fits
+        let values = [
+            0u128,
+            1,
+            2,
+            0xffff_ffff,
+            1 << 32,
+            123_456_789_012,
+            u64::MAX as u128,
+        ];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigUint::from(a), BigUint::from(b));
+                assert_eq!((&x + &y).to_u128(), Some(a + b), "{a} + {b}");
+                assert_eq!((&x * &y).to_u128(), Some(a * b), "{a} * {b}");
+                assert_eq!(
+                    x.checked_sub(&y).and_then(|d| d.to_u128()),
+                    a.checked_sub(b)
+                );
+                assert_eq!(x.cmp(&y), a.cmp(&b), "{a} {b}");
+                if let (Some(q), Some(r)) = (a.checked_div(b), a.checked_rem(b)) {
+                    let (big_q, big_r) = x.div_rem(&y);
+                    assert_eq!((big_q.to_u128(), big_r.to_u128()), (Some(q), Some(r)));
+                }
+            }
+            let x = BigUint::from(a);
+            for shift in [0, 1, 31, 32, 33, 63] {
+                assert_eq!((&x << shift).to_u128(), Some(a << shift), "{a} << {shift}");
+                assert_eq!((&x >> shift).to_u128(), Some(a >> shift), "{a} >> {shift}");
+            }
+            assert_eq!(x.bits(), 128 - a.leading_zeros());
+        }
+
+        let a = big("340282366920938463463374607431768211456");
+        assert_eq!(a.to_u128(), None);
+        assert_eq!(&a - &BigUint::one(), BigUint::from(u128::MAX));
+        assert_eq!(
+            (&a * &a).to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        );
+        assert_eq!(&(&a * &a) >> 256, BigUint::one());
+        assert_eq!(a.div_rem_small(7).1, (u128::MAX % 7 + 1) as u32 % 7);
+
+        let (q, r) = (&a * &a).div_rem(&(&a + &BigUint::from(3u8)));
+        assert_eq!(&(&q * &(&a + &BigUint::from(3u8))) + &r, &a * &a);
+        assert!(r < &a + &BigUint::from(3u8));
+        assert_eq!(
+            BigUint::from(100u8).div_rem(&BigUint::from(7u8)),
+            (14u8.into(), 2u8.into())
+        );
+        assert_eq!(
+            BigUint::zero().div_rem(&a),
+            (BigUint::zero(), BigUint::zero())
+        );
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..5_000u128 {
+            assert_eq!(BigUint::from(n).isqrt().to_u128(), Some(n.isqrt()), "{n}");
+        }
+
+        let r = big("98765432109876543210987654321");
+        let square = &r * &r;
+        assert_eq!(square.isqrt(), r);
+        assert_eq!((&square - &BigUint::one()).isqrt(), &r - &BigUint::one());
+        assert_eq!((&square + &(&r + &r)).isqrt(), r);
+    }
+}
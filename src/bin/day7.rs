@@ -1,274 +1,82 @@
-use std::{cmp, collections::HashMap, fmt::Display, marker::PhantomData, ops::Deref, str::FromStr};
-
-use aoc2023::aoc;
-
-#[derive(PartialEq, Eq, Clone, Copy)]
-struct Hand([u8; 5]);
-
-impl Display for Hand {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for c in self.0 {
-            write!(f, "{}", c as char)?
-        }
-        Ok(())
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum HandType {
-    FiveOfAKind = 0, // 1 distinct, _
-    FourOfAKind,     // 2 distinct, max freq 4
-    FullHouse,       // 2 distinct, max freq 3
-    ThreeOfAKind,    // 3 distinct, max freq 3
-    TwoPair,         // 3 distinct, max freq 2
-    OnePair,         // 4 distinct, _
-    High,            // 5 distinct, _
-}
-
-impl HandType {
-    pub const fn rank(&self) -> u8 {
-        (HandType::High as u8) - (*self as u8)
-    }
-}
-
-#[derive(Clone, Copy)]
-struct Round {
-    hand: Hand,
-    bid: usize,
-}
-
-#[derive(Debug)]
-struct ParseRoundError(String);
-
-impl FromStr for Round {
-    type Err = ParseRoundError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (hand, bid) = s
-            .split_once(' ')
-            .ok_or_else(|| ParseRoundError(s.to_owned()))?;
-
-        Ok(Self {
-            hand: Hand(
-                hand.as_bytes()
-                    .try_into()
-                    .map_err(|_| ParseRoundError(s.to_owned()))?,
-            ),
-            bid: bid.parse().map_err(|_| ParseRoundError(s.to_owned()))?,
-        })
-    }
-}
-
-struct Rounds(Vec<Round>);
-
-impl FromStr for Rounds {
-    type Err = ParseRoundError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines()
-                .map(|line| line.parse())
-                .collect::<Result<_, _>>()?,
-        ))
-    }
-}
-
-impl Deref for Rounds {
-    type Target = Vec<Round>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-trait HandRank {
-    fn hand_type(hand: &Hand) -> HandType;
-    fn card_rank(card: u8) -> u8;
-}
-
-struct HandRanker<T: HandRank>(PhantomData<T>);
-
-impl<T: HandRank> HandRanker<T> {
-    fn rank_array(hand: &Hand) -> [u8; 5] {
-        hand.0.map(|c| T::card_rank(c))
-    }
-
-    fn rank_pair(hand: &Hand) -> (u8, [u8; 5]) {
-        (T::hand_type(hand).rank(), Self::rank_array(hand))
-    }
-
-    fn cmp(a: &Hand, b: &Hand) -> cmp::Ordering {
-        Self::rank_pair(a).cmp(&Self::rank_pair(b))
-    }
-
-    pub fn rank<'a>(rounds: &'a Rounds) -> Vec<(usize, &'a Round)> {
-        let mut ranked: Vec<&Round> = rounds.0.iter().collect();
-
-        ranked.sort_by(|a, b| Self::cmp(&a.hand, &b.hand));
-
-        ranked
-            .into_iter()
-            .enumerate()
-            .map(|(i, round)| {
-                #[cfg(test)]
-                println!(
-                    "{} -> {} {}, {:?}=({})",
-                    i + 1,
-                    round.hand,
-                    round.bid,
-                    T::hand_type(&round.hand),
-                    T::hand_type(&round.hand).rank(),
-                );
-                (i + 1, round)
-            })
-            .collect()
-    }
+use std::path::Path;
+
+use aoc2023::{
+    aoc::cli::Cli,
+    days::day7::{self, Day7, Rules},
+};
+use clap::{error::ErrorKind, CommandFactory, Parser};
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(flatten)]
+    cli: Cli,
+
+    /// Only print the winnings under these rules: one of `standard`, `jokers`
+    /// or `jokers-high`, or a rules file
+    #[arg(long, value_name = "NAME|FILE")]
+    rules: Option<String>,
+
+    /// Print a table of how every round was ranked, for both parts or for
+    /// the rules given with --rules
+    #[arg(long)]
+    explain: bool,
+
+    /// Print an input of this many random rounds instead of solving
+    #[arg(long, value_name = "ROUNDS", conflicts_with_all = ["rules", "explain"])]
+    generate: Option<usize>,
+
+    /// Seed for --generate
+    #[arg(long, default_value_t = 1, requires = "generate")]
+    seed: u64,
 }
 
-mod part1 {
-    use super::*;
-
-    pub struct HandRankPart1;
-
-    impl HandRank for HandRankPart1 {
-        fn hand_type(hand: &Hand) -> HandType {
-            let counts = hand.0.iter().fold(HashMap::new(), |mut freq, c| {
-                *freq.entry(*c).or_insert(0u8) += 1;
-                freq
-            });
-
-            let distinct = counts.len();
-            let max_freq = counts.values().copied().max().unwrap();
-
-            match (distinct, max_freq) {
-                (1, _) => HandType::FiveOfAKind,
-                (2, 4) => HandType::FourOfAKind,
-                (2, 3) => HandType::FullHouse,
-                (3, 3) => HandType::ThreeOfAKind,
-                (3, 2) => HandType::TwoPair,
-                (4, _) => HandType::OnePair,
-                (5, _) => HandType::High,
-                _ => unreachable!(),
-            }
-        }
-
-        fn card_rank(c: u8) -> u8 {
-            match c {
-                b'A' => 14,
-                b'K' => 13,
-                b'Q' => 12,
-                b'J' => 11,
-                b'T' => 10,
-                b'2'..=b'9' => c - b'0',
-                _ => panic!("Invalid card {}", c),
-            }
-        }
-    }
-
-    pub fn calculate(rounds: &Rounds) -> usize {
-        HandRanker::<HandRankPart1>::rank(rounds)
-            .iter()
-            .map(|(i, round)| i * round.bid)
-            .sum()
-    }
-
-    #[cfg(test)]
-    mod test {
-        use super::*;
-
-        #[test]
-        fn test_card_rank() {
-            assert_eq!(HandRankPart1::card_rank(b'2'), 2);
-            assert_eq!(HandRankPart1::card_rank(b'9'), 9);
-        }
-
-        #[test]
-        fn test_example() {
-            let rounds = parse_input(&aoc::example::example_string("day7.txt"));
-
-            assert_eq!(calculate(&rounds), 6440);
+/// Built-in rules by name, or else rules from the file at `name`.
+fn load_rules(name: &str) -> Rules {
+    match Rules::builtin(name) {
+        Some(rules) => rules,
+        None if Path::new(name).exists() => {
+            Rules::load(Path::new(name)).unwrap_or_else(|e| e.exit())
         }
+        None => Args::command()
+            .error(
+                ErrorKind::InvalidValue,
+                format!(
+                    "`{name}` is neither a rules file nor one of {}",
+                    Rules::BUILTIN.join(", ")
+                ),
+            )
+            .exit(),
     }
 }
 
-mod part2 {
-    use super::*;
-
-    struct HandRankPart2;
-
-    impl HandRank for HandRankPart2 {
-        fn hand_type(hand: &Hand) -> HandType {
-            let mut counts = hand.0.iter().fold(HashMap::new(), |mut freq, c| {
-                *freq.entry(*c).or_insert(0u8) += 1;
-                freq
-            });
-
-            let jokers = counts.remove(&b'J').unwrap_or(0);
-            let distinct = counts.len().max(1);
-            let max_freq = counts.values().copied().max().unwrap_or(0) + jokers;
-
-            #[cfg(test)]
-            println!(
-                "{} -> jokers={}, ({}, {})",
-                hand, jokers, distinct, max_freq
-            );
-            match (distinct, max_freq) {
-                (1, _) => HandType::FiveOfAKind,
-                (2, 4) => HandType::FourOfAKind,
-                (2, 3) => HandType::FullHouse,
-                (3, 3) => HandType::ThreeOfAKind,
-                (3, 2) => HandType::TwoPair,
-                (4, _) => HandType::OnePair,
-                (5, _) => HandType::High,
-                _ => unreachable!(),
-            }
-        }
-
-        fn card_rank(c: u8) -> u8 {
-            if c == b'J' {
-                1
-            } else {
-                part1::HandRankPart1::card_rank(c)
-            }
-        }
-    }
+fn main() {
+    let args = Args::parse();
 
-    pub fn calculate(rounds: &Rounds) -> usize {
-        HandRanker::<HandRankPart2>::rank(rounds)
-            .iter()
-            .map(|(i, round)| i * round.bid)
-            .sum()
+    if let Some(rounds) = args.generate {
+        print!("{}", day7::generate_input(rounds, args.seed));
+        return;
     }
 
-    #[cfg(test)]
-    mod test {
-        use super::*;
-
-        #[test]
-        fn test_card_rank() {
-            assert_eq!(HandRankPart2::card_rank(b'2'), 2);
-            assert_eq!(HandRankPart2::card_rank(b'9'), 9);
-            assert_eq!(HandRankPart2::card_rank(b'J'), 1);
+    let rule_sets = match (&args.rules, args.explain) {
+        (Some(name), _) => vec![(name.clone(), load_rules(name))],
+        (None, true) => vec![
+            ("Part 1".to_owned(), Rules::part1()),
+            ("Part 2".to_owned(), Rules::part2()),
+        ],
+        (None, false) => return args.cli.run(&Day7),
+    };
+
+    let rounds = args.cli.parse_input::<Day7>().unwrap_or_else(|e| e.exit());
+    for (i, (name, rules)) in rule_sets.iter().enumerate() {
+        if !args.explain {
+            println!("Winnings: {}", rules.winnings(&rounds));
+            continue;
         }
 
-        #[test]
-        fn test_example() {
-            let rounds = parse_input(&aoc::example::example_string("day7.txt"));
-
-            assert_eq!(calculate(&rounds), 5905);
+        if i > 0 {
+            println!();
         }
+        println!("{name}");
+        print!("{}", rules.explain(&rounds));
     }
 }
-
-fn parse_input(s: &str) -> Rounds {
-    s.parse().unwrap()
-}
-
-fn main() {
-    let cli = aoc::cli::parse();
-
-    let rounds: Rounds = parse_input(&cli.input_string());
-
-    println!("Part 1: {}", part1::calculate(&rounds));
-    println!("Part 2: {}", part2::calculate(&rounds));
-}
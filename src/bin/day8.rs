@@ -1,231 +1,95 @@
-use std::{
-    collections::HashMap,
-    fmt::{Display, Write},
-    str::from_utf8,
+use aoc2023::{
+    aoc::cli::Cli,
+    days::day8::{Day8, NodeSet, Pattern},
 };
-
-use aoc2023::aoc;
-
-type Node = [u8; 3];
-
-trait StartEnd {
-    fn is_start(&self) -> bool;
-    fn is_end(&self) -> bool;
-}
-
-impl StartEnd for Node {
-    fn is_start(&self) -> bool {
-        self[2] == b'A'
-    }
-
-    fn is_end(&self) -> bool {
-        self[2] == b'Z'
-    }
-}
-
-#[derive(Debug)]
-struct Input {
-    steps: Vec<Step>,
-    network: HashMap<Node, (Node, Node)>,
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(flatten)]
+    cli: Cli,
+
+    /// Walk from the nodes matching this instead of solving: a node name,
+    /// `*<suffix>` or `/<regex>/`. May be given more than once
+    #[arg(long, value_name = "PATTERN")]
+    start: Vec<Pattern>,
+
+    /// Where walks from --start stop, in the same form. Defaults to ZZZ
+    #[arg(long, value_name = "PATTERN")]
+    goal: Vec<Pattern>,
+
+    /// Print every node on each walk
+    #[arg(long)]
+    path: bool,
+
+    /// Print the network as a Graphviz digraph instead of solving
+    #[arg(long)]
+    dot: bool,
+
+    /// Draw the walks from --start, or else the part 1 walk, on the --dot
+    /// output
+    #[arg(long, requires = "dot")]
+    with_path: bool,
 }
 
-impl Input {
-    pub fn iter_steps(&self) -> StepsIter {
-        StepsIter::new(&self.steps)
-    }
-
-    pub fn next(&self, start: &Node, step: Step) -> &Node {
-        let node = self.network.get(start).unwrap();
-        match step {
-            Step::L => &node.0,
-            Step::R => &node.1,
+impl Args {
+    fn starts(&self) -> NodeSet {
+        match self.start.is_empty() {
+            true => NodeSet::exact(*b"AAA"),
+            false => NodeSet(self.start.clone()),
         }
     }
-}
-
-impl Display for Input {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for step in &self.steps {
-            write!(f, "{:?}", step)?;
-        }
-        f.write_str("\n\n")?;
 
-        for (k, (l, r)) in &self.network {
-            writeln!(
-                f,
-                "{} => ({}, {})",
-                from_utf8(k).unwrap(),
-                from_utf8(l).unwrap(),
-                from_utf8(r).unwrap()
-            )?;
+    fn goals(&self) -> NodeSet {
+        match self.goal.is_empty() {
+            true => NodeSet::exact(*b"ZZZ"),
+            false => NodeSet(self.goal.clone()),
         }
-
-        Ok(())
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Step {
-    L,
-    R,
-}
-
-impl Display for Step {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_char(match self {
-            Step::L => 'L',
-            Step::R => 'R',
-        })
     }
 }
 
-struct StepsIter<'a> {
-    steps: &'a Vec<Step>,
-    idx: usize,
-}
-
-impl<'a> StepsIter<'a> {
-    pub fn new(steps: &'a Vec<Step>) -> Self {
-        Self { steps, idx: 0 }
-    }
-}
-
-impl<'a> Iterator for StepsIter<'a> {
-    type Item = Step;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let step = self.steps[self.idx];
-        self.idx = (self.idx + 1) % self.steps.len();
-        Some(step)
-    }
-}
-
-mod part1 {
-    use super::*;
-
-    pub fn calculate(input: &Input) -> usize {
-        let mut node: &Node = b"AAA";
-
-        for (i, step) in input.iter_steps().enumerate() {
-            if node == b"ZZZ" {
-                return i;
-            }
-            node = input.next(node, step);
-        }
+fn main() {
+    let args = Args::parse();
 
-        unreachable!()
+    if !args.dot && args.start.is_empty() && args.goal.is_empty() {
+        return args.cli.run(&Day8);
     }
 
-    #[cfg(test)]
-    mod test {
-        use super::*;
-
-        #[test]
-        fn test_example() {
-            assert_eq!(
-                calculate(&parse_input(aoc::example::example_lines("day8_1.txt"))),
-                2
-            );
-            assert_eq!(
-                calculate(&parse_input(aoc::example::example_lines("day8_2.txt"))),
-                6
-            );
-        }
+    let input = args.cli.parse_input::<Day8>().unwrap_or_else(|e| e.exit());
+    let (starts, goals) = (args.starts(), args.goals());
+    let walks = || {
+        input
+            .nodes(&starts)
+            .map(|start| input.walk(start, &goals).unwrap_or_else(|e| e.exit()))
+            .collect::<Vec<_>>()
+    };
+    let name = |node| String::from_utf8_lossy(input.name(node)).into_owned();
+
+    if args.dot {
+        let paths = match args.with_path {
+            true => walks(),
+            false => vec![],
+        };
+        // Without a query, highlight where the ghosts start and finish
+        let (starts, goals) = match args.start.is_empty() && args.goal.is_empty() {
+            true => (NodeSet::ghost_starts(), NodeSet::ghost_ends()),
+            false => (starts.clone(), goals.clone()),
+        };
+        print!("{}", input.to_dot(&starts, &goals, &paths));
+        return;
     }
-}
-
-mod part2 {
-    use super::*;
-    use aoc2023::quant::QuantIter;
-    use itertools::Itertools;
-
-    pub fn calculate(input: &Input) -> usize {
-        let starts: Vec<_> = input
-            .network
-            .keys()
-            .cloned()
-            .filter(Node::is_start)
-            .collect();
 
-        starts
-            .into_iter()
-            .map(|start| {
-                let mut node = &start;
-                let mut to_end = 0;
-                let mut step_iter = input.iter_steps();
-                let _end = loop {
-                    let step = step_iter.next().unwrap();
-                    if node.is_end() {
-                        break *node;
-                    }
-                    node = input.next(&node, step);
-                    to_end += 1;
-                };
-
-                #[cfg(test)]
-                println!(
-                    "{} -> {} to_end={}",
-                    from_utf8(&start).unwrap(),
-                    from_utf8(&_end).unwrap(),
-                    to_end
-                );
-
-                to_end
-            })
-            .lcm()
-            .unwrap()
-    }
-
-    #[cfg(test)]
-    mod test {
-        use super::*;
-
-        #[test]
-        fn test_example() {
-            assert_eq!(
-                calculate(&parse_input(aoc::example::example_lines("day8_3.txt"))),
-                6
-            );
+    for path in walks() {
+        let (first, last) = (path[0], *path.last().unwrap());
+        println!(
+            "{} -> {}: {} steps",
+            name(first),
+            name(last),
+            path.len() - 1
+        );
+        if args.path {
+            let nodes: Vec<_> = path.iter().map(|&node| name(node)).collect();
+            println!("  {}", nodes.join(" "));
         }
     }
 }
-
-fn parse_input(mut lines: impl Iterator<Item = String>) -> Input {
-    let steps = lines.next().expect("Has steps");
-    let steps = steps
-        .chars()
-        .into_iter()
-        .filter_map(|step| match step {
-            'L' => Some(Step::L),
-            'R' => Some(Step::R),
-            _ => None,
-        })
-        .collect();
-
-    drop(lines.next());
-
-    let network = lines
-        .map(|line| {
-            let line = line.as_bytes();
-
-            (
-                line[0..3].try_into().unwrap(),
-                (
-                    line[7..10].try_into().unwrap(),
-                    line[12..15].try_into().unwrap(),
-                ),
-            )
-        })
-        .collect();
-
-    Input { steps, network }
-}
-
-fn main() {
-    let cli = aoc::cli::parse();
-
-    let input = parse_input(cli.line_reader());
-
-    println!("Part 1: {}", part1::calculate(&input));
-    println!("Part 2: {}", part2::calculate(&input));
-}
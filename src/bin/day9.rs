@@ -1,89 +1,77 @@
-use aoc2023::aoc;
-
-type Sequence = Vec<isize>;
-
-fn parse_input(lines: impl Iterator<Item = String>) -> Vec<Sequence> {
-    lines
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|n| n.parse().unwrap())
-                .collect()
-        })
-        .collect()
+use aoc2023::{
+    aoc::cli::Cli,
+    days::day9::{checked_sum, classify, fit, fit_all, value_at, Day9, Suspicious},
+    poly::Shape,
+};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(flatten)]
+    cli: Cli,
+
+    /// Print each sequence's value at this position instead of solving,
+    /// counting its first value as 0. Negative positions come before it
+    #[arg(long, value_name = "POSITION", allow_negative_numbers = true)]
+    at: Option<i128>,
+
+    /// Print each sequence's shape and Newton coefficients instead of
+    /// solving
+    #[arg(long, conflicts_with = "at")]
+    fit: bool,
+
+    /// What to do with sequences that aren't clearly polynomial: `fail`, or
+    /// `skip` them with a warning
+    #[arg(long, value_name = "POLICY", default_value = "fail")]
+    suspicious: Suspicious,
 }
 
-mod part1 {
-    use itertools::Itertools;
-
-    use super::*;
-
-    pub fn calculate(sequences: &Vec<Sequence>) -> isize {
-        sequences.iter().map(extrapolate).sum()
-    }
-
-    fn extrapolate(seq: &Sequence) -> isize {
-        if is_all_zero(seq) {
-            0
-        } else {
-            seq.last().unwrap() + extrapolate(&diff_seq(seq))
-        }
-    }
-
-    pub fn diff_seq(seq: &Sequence) -> Sequence {
-        seq.iter().tuple_windows().map(|(a, b)| b - a).collect()
-    }
+fn main() {
+    let args = Args::parse();
 
-    pub fn is_all_zero(seq: &Sequence) -> bool {
-        seq.iter().all(|x| *x == 0)
+    if args.at.is_none() && !args.fit && args.suspicious == Suspicious::Fail {
+        return args.cli.run(&Day9);
     }
 
-    #[cfg(test)]
-    mod test {
-        use super::*;
-
-        #[test]
-        fn test_example() {
-            let sequences = parse_input(aoc::example::example_lines("day9.txt"));
-
-            assert_eq!(calculate(&sequences), 114)
+    let sequences = args.cli.parse_input::<Day9>().unwrap_or_else(|e| e.exit());
+
+    if args.fit {
+        for (i, seq) in sequences.iter().enumerate() {
+            let newton = fit(seq).unwrap_or_else(|e| e.exit());
+            let coefficients: Vec<_> = newton.coefficients().iter().map(i128::to_string).collect();
+            println!(
+                "line {}: {}: {}",
+                i + 1,
+                newton.shape(),
+                coefficients.join(" ")
+            );
         }
-    }
-}
-
-mod part2 {
-    use super::part1::{diff_seq, is_all_zero};
-    use super::*;
-
-    pub fn calculate(sequences: &Vec<Sequence>) -> isize {
-        sequences.iter().map(extrapolate_backwards).sum()
+        return;
     }
 
-    fn extrapolate_backwards(seq: &Sequence) -> isize {
-        if is_all_zero(seq) {
-            0
-        } else {
-            seq[0] - extrapolate_backwards(&diff_seq(seq))
+    let fits = fit_all(&sequences, args.suspicious).unwrap_or_else(|e| e.exit());
+    for (line, shape) in classify(&sequences).unwrap_or_else(|e| e.exit()) {
+        if !matches!(shape, Shape::Polynomial(_)) {
+            eprintln!("skipping line {line}, which is {shape}");
         }
     }
 
-    #[cfg(test)]
-    mod test {
-        use super::*;
-
-        #[test]
-        fn test_example() {
-            let sequences = parse_input(aoc::example::example_lines("day9.txt"));
-
-            assert_eq!(calculate(&sequences), 2)
+    let total = |position: &dyn Fn(&_) -> i128| {
+        checked_sum(fits.iter().map(|(line, newton)| {
+            let value = value_at(newton, position(newton))?;
+            if args.at.is_some() {
+                println!("line {line}: {value}");
+            }
+            Ok(value)
+        }))
+        .unwrap_or_else(|e| e.exit())
+    };
+
+    match args.at {
+        Some(at) => println!("Total: {}", total(&|_| at)),
+        None => {
+            println!("Part 1: {}", total(&|newton| newton.samples() as i128));
+            println!("Part 2: {}", total(&|_| -1));
         }
     }
 }
-
-fn main() {
-    let cli = aoc::cli::parse();
-
-    let sequences = parse_input(cli.line_reader());
-
-    println!("Part 1: {}", part1::calculate(&sequences));
-    println!("Part 2: {}", part2::calculate(&sequences));
-}
@@ -0,0 +1,28 @@
+use crate::aoc::DynSolution;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Every solved day, in calendar order.
+pub static ALL: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn DynSolution> {
+    ALL.iter().copied().find(|solution| solution.day() == day)
+}
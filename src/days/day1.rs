@@ -0,0 +1,130 @@
+use crate::aoc::{Result, Solution};
+
+mod part1 {
+    use crate::aoc::{parse::parse_lines, Error, Result};
+
+    fn parse_line(line: &str) -> Result<usize> {
+        let mut digits = line
+            .bytes()
+            .filter(|&b| b'0' < b && b <= b'9')
+            .map(|b| (b - b'0') as usize);
+
+        let first = digits
+            .next()
+            .ok_or_else(|| Error::parse(line, line, "line has no digits"))?;
+        let last = digits.next_back().unwrap_or(first);
+
+        Ok(first * 10 + last)
+    }
+
+    pub fn calculate(input: &str) -> Result<usize> {
+        Ok(parse_lines(input, parse_line)?.into_iter().sum())
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::calculate;
+        use crate::aoc;
+
+        #[test]
+        fn test_example() {
+            let input = aoc::example::example_string("day1_1.txt").unwrap();
+
+            assert_eq!(calculate(&input).unwrap(), 142);
+        }
+    }
+}
+
+mod part2 {
+    use crate::aoc::{parse::parse_lines, Error, Result};
+
+    static NUMBERS: phf::Map<&'static [u8], usize> = phf::phf_map!(
+        b"one" => 1,
+        b"two" => 2,
+        b"three" => 3,
+        b"four" => 4,
+        b"five" => 5,
+        b"six" => 6,
+        b"seven" => 7,
+        b"eight" => 8,
+        b"nine" => 9,
+        b"1" => 1,
+        b"2" => 2,
+        b"3" => 3,
+        b"4" => 4,
+        b"5" => 5,
+        b"6" => 6,
+        b"7" => 7,
+        b"8" => 8,
+        b"9" => 9,
+    );
+
+    fn parse_line(line: &str) -> Result<usize> {
+        let s = line.as_bytes();
+
+        let numbers = (0..s.len())
+            .filter_map(|i| {
+                let s = &s[i..s.len()];
+                for (word, n) in &NUMBERS {
+                    if s.starts_with(word) {
+                        return Some(*n);
+                    }
+                }
+                None
+            })
+            .collect::<Vec<_>>();
+
+        match (numbers.first(), numbers.last()) {
+            (Some(first), Some(last)) => Ok(first * 10 + last),
+            _ => Err(Error::parse(line, line, "line has no digits")),
+        }
+    }
+
+    pub fn calculate(input: &str) -> Result<usize> {
+        let numbers = parse_lines(input, |line| {
+            let number = parse_line(line)?;
+
+            #[cfg(test)]
+            eprintln!("{} {}", line, number);
+
+            Ok(number)
+        })?;
+
+        Ok(numbers.into_iter().sum())
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::calculate;
+        use crate::aoc;
+
+        #[test]
+        fn test_example() {
+            let input = aoc::example::example_string("day1_2.txt").unwrap();
+
+            assert_eq!(calculate(&input).unwrap(), 281);
+        }
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        part1::calculate(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2::calculate(input)
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::aoc::{
+    parse::{parse_lines, parse_num},
+    Error, Result, Solution,
+};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Red,
+    Blue,
+    Green,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    id: usize,
+    max: HashMap<Colour, usize>,
+}
+
+impl Game {
+    pub fn power(&self) -> usize {
+        self.max.values().product()
+    }
+}
+
+fn parse_game(line: &str) -> Result<Game> {
+    let (id, s) = line
+        .split_once(": ")
+        .ok_or_else(|| Error::parse(line, line, "expected `Game <id>: `"))?;
+    let id = id
+        .strip_prefix("Game ")
+        .ok_or_else(|| Error::parse(line, id, "expected `Game <id>: `"))?;
+    let id = parse_num(line, id)?;
+
+    let mut max = HashMap::new();
+    let re = Regex::new(r"(\d+) ([a-z]+)").unwrap();
+    for (_, [count, colour]) in re.captures_iter(s).map(|c| c.extract()) {
+        let colour = parse_colour(colour)
+            .ok_or_else(|| Error::parse(line, colour, format!("unknown colour `{colour}`")))?;
+        let count: usize = parse_num(line, count)?;
+        let current_max = *max.entry(colour).or_insert(0);
+        if count > current_max {
+            max.insert(colour, count);
+        }
+    }
+
+    Ok(Game { id, max })
+}
+
+fn parse_colour(s: &str) -> Option<Colour> {
+    match s {
+        "red" => Some(Colour::Red),
+        "green" => Some(Colour::Green),
+        "blue" => Some(Colour::Blue),
+        _ => None,
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(games: &[Game]) -> usize {
+        let limits: &[(Colour, usize)] =
+            &[(Colour::Red, 12), (Colour::Green, 13), (Colour::Blue, 14)];
+
+        games
+            .iter()
+            .filter(|game| {
+                limits
+                    .iter()
+                    .all(|&(colour, limit)| *game.max.get(&colour).unwrap_or(&0) <= limit)
+            })
+            .map(|game| game.id)
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let games =
+                parse_input(&crate::aoc::example::example_string("day2.txt").unwrap()).unwrap();
+            assert_eq!(calculate(&games), 8);
+        }
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(games: &[Game]) -> usize {
+        games.iter().map(|game| game.power()).sum()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let games =
+                parse_input(&crate::aoc::example::example_string("day2.txt").unwrap()).unwrap();
+            assert_eq!(calculate(&games), 2286);
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Game>> {
+    parse_lines(input, parse_game)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1::calculate(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2::calculate(input))
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    aoc::{parse::parse_num, Result, Solution},
+    grid::{Grid, Point},
+};
+
+#[derive(Debug)]
+pub struct Schematic {
+    grid: Grid<char>,
+    parts: Vec<Part>,
+    /// Which of `parts` covers each cell, if any.
+    part_at: Grid<Option<usize>>,
+}
+
+#[derive(Debug)]
+struct Part {
+    number: usize,
+    points: Vec<Point>,
+}
+
+impl Schematic {
+    fn is_symbol(c: char) -> bool {
+        c != '.' && !c.is_ascii_digit()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = (Point, char)> + '_ {
+        self.grid
+            .iter()
+            .filter(|(_, &c)| Self::is_symbol(c))
+            .map(|(point, &c)| (point, c))
+    }
+
+    /// The distinct parts with a digit next to `point`.
+    fn parts_around(&self, point: Point) -> impl Iterator<Item = &Part> {
+        self.part_at
+            .neighbours8(point)
+            .filter_map(|(_, part)| *part)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|i| &self.parts[i])
+    }
+
+    fn is_adjacent_to_symbol(&self, part: &Part) -> bool {
+        part.points.iter().any(|&point| {
+            self.grid
+                .neighbours8(point)
+                .any(|(_, &c)| Self::is_symbol(c))
+        })
+    }
+}
+
+fn parse_input(input: &str) -> Result<Schematic> {
+    let grid: Grid<char> = input.parse()?;
+    let mut parts: Vec<Part> = vec![];
+    let mut part_at = Grid::filled(grid.width(), grid.height(), None);
+
+    for (y, line) in input.lines().enumerate() {
+        // Start of the current number, as a column and a byte offset
+        let mut start: Option<(usize, usize)> = None;
+
+        // Run one past the end of the line so a number at the end gets added
+        let cells = line.char_indices().map(Some).chain([None]);
+        for (x, cell) in cells.enumerate() {
+            match (cell, start) {
+                (Some((i, c)), None) if c.is_ascii_digit() => start = Some((x, i)),
+                (Some((_, c)), Some(_)) if c.is_ascii_digit() => {}
+                (cell, Some((start_x, start_i))) => {
+                    let end_i = cell.map_or(line.len(), |(i, _)| i);
+                    let points: Vec<_> = (start_x..x)
+                        .map(|x| Point::new(x as isize, y as isize))
+                        .collect();
+                    for &point in &points {
+                        part_at[point] = Some(parts.len());
+                    }
+
+                    parts.push(Part {
+                        number: parse_num(input, &line[start_i..end_i])?,
+                        points,
+                    });
+                    start = None;
+                }
+                (_, None) => {}
+            }
+        }
+    }
+
+    Ok(Schematic {
+        grid,
+        parts,
+        part_at,
+    })
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(schematic: &Schematic) -> usize {
+        schematic
+            .parts
+            .iter()
+            .filter(|part| schematic.is_adjacent_to_symbol(part))
+            .map(|part| part.number)
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let schematic =
+                parse_input(&crate::aoc::example::example_string("day3.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&schematic), 4361);
+        }
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(schematic: &Schematic) -> usize {
+        schematic
+            .symbols()
+            .filter(|&(_, c)| c == '*')
+            .map(|(point, _)| {
+                let parts: Vec<_> = schematic.parts_around(point).collect();
+
+                match parts[..] {
+                    [a, b] => a.number * b.number,
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let schematic =
+                parse_input(&crate::aoc::example::example_string("day3.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&schematic), 467835);
+        }
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1::calculate(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2::calculate(input))
+    }
+}
@@ -0,0 +1,167 @@
+use crate::aoc::{
+    parse::{parse_lines, parse_num},
+    Error, Result, Solution,
+};
+
+#[derive(Debug)]
+pub struct Input {
+    cards: Vec<Card>,
+}
+
+impl Input {
+    pub fn get_card(&self, id: usize) -> Option<&Card> {
+        self.cards.get(id - 1)
+    }
+
+    pub fn cards_won_from_card(&self, id: usize) -> impl Iterator<Item = &Card> {
+        let card = self.get_card(id).unwrap();
+        let count = card.win_count();
+
+        (id + 1..(id + count + 1)).filter_map(|id| self.get_card(id))
+    }
+}
+
+#[derive(Debug)]
+pub struct Card {
+    id: usize,
+    winners: Vec<usize>,
+    have: Vec<usize>,
+}
+
+impl Card {
+    pub fn win_count(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|have| self.winners.contains(have))
+            .count()
+    }
+
+    pub fn points(&self) -> usize {
+        let win_count = self.win_count();
+        if win_count > 0 {
+            2usize.pow((win_count - 1) as u32)
+        } else {
+            0
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let cards = parse_lines(input, parse_line)?;
+
+    // Cards are looked up by id, so they have to be numbered from 1 in order
+    if let Some((i, card)) = cards.iter().enumerate().find(|(i, card)| card.id != i + 1) {
+        let line = input.lines().nth(i).unwrap_or_default();
+        return Err(Error::parse(
+            input,
+            line,
+            format!("expected card {}, found card {}", i + 1, card.id),
+        ));
+    }
+
+    Ok(Input { cards })
+}
+
+fn parse_line(line: &str) -> Result<Card> {
+    let (id, rest) = line
+        .split_once(": ")
+        .ok_or_else(|| Error::parse(line, line, "expected `Card <id>: `"))?;
+    let id = id
+        .split_ascii_whitespace()
+        .nth(1)
+        .ok_or_else(|| Error::parse(line, id, "expected `Card <id>: `"))?;
+    let id = parse_num(line, id)?;
+
+    let (winners, have) = rest
+        .split_once(" | ")
+        .ok_or_else(|| Error::parse(line, rest, "expected `<winners> | <have>`"))?;
+
+    Ok(Card {
+        id,
+        winners: winners
+            .split_ascii_whitespace()
+            .map(|n| parse_num(line, n))
+            .collect::<Result<_>>()?,
+        have: have
+            .split_ascii_whitespace()
+            .map(|n| parse_num(line, n))
+            .collect::<Result<_>>()?,
+    })
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(input: &Input) -> usize {
+        input.cards.iter().map(|card| card.points()).sum()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let input =
+                parse_input(&crate::aoc::example::example_string("day4.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&input), 13);
+        }
+    }
+}
+
+mod part2 {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    pub fn calculate(input: &Input) -> usize {
+        let mut card_count: HashMap<usize, usize> =
+            input.cards.iter().map(|card| (card.id, 1)).collect();
+
+        for card in &input.cards {
+            let copies = *card_count.get(&card.id).unwrap();
+
+            for won_card in input.cards_won_from_card(card.id) {
+                *card_count.get_mut(&won_card.id).unwrap() += copies;
+            }
+        }
+
+        card_count.values().sum()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let input =
+                parse_input(&crate::aoc::example::example_string("day4.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&input), 30);
+        }
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1::calculate(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2::calculate(input))
+    }
+}
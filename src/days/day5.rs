@@ -0,0 +1,539 @@
+use crate::{
+    aoc::{Error, Result, Solution},
+    arr_chunks,
+    interval::{Interval, IntervalSet, PiecewiseMap, Segment},
+};
+use nom;
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<usize>,
+    maps: Vec<Map>,
+    /// Every map in turn, taking a seed straight to its location.
+    composed: PiecewiseMap<usize>,
+}
+
+impl Almanac {
+    pub fn new(seeds: Vec<usize>, maps: Vec<Map>) -> Self {
+        let composed = maps.iter().fold(PiecewiseMap::identity(), |composed, map| {
+            composed.then(&map.map)
+        });
+
+        Self {
+            seeds,
+            maps,
+            composed,
+        }
+    }
+
+    pub fn maps(&self) -> &[Map] {
+        &self.maps
+    }
+
+    /// The seed to location map.
+    pub fn composed(&self) -> &PiecewiseMap<usize> {
+        &self.composed
+    }
+
+    /// The map taking `source` values to `destination` values through
+    /// however many maps lie between them, if the chain goes that way.
+    pub fn between(&self, source: &str, destination: &str) -> Option<PiecewiseMap<usize>> {
+        let start = self.maps.iter().position(|map| map.source == source)?;
+        let mut composed = PiecewiseMap::identity();
+
+        for map in &self.maps[start..] {
+            composed = composed.then(&map.map);
+            if map.destination == destination {
+                return Some(composed);
+            }
+        }
+
+        None
+    }
+
+    pub fn convert(&self, seed: usize) -> usize {
+        self.composed.convert(seed)
+    }
+
+    pub fn convert_seed_ranges(&self, seeds: &IntervalSet<usize>) -> IntervalSet<usize> {
+        self.composed.convert_set(seeds)
+    }
+
+    pub fn locations(&self) -> Vec<usize> {
+        self.seeds.iter().map(|seed| self.convert(*seed)).collect()
+    }
+
+    /// The seeds read as pairs of start and length. Parsing rejects ranges
+    /// that run past `usize::MAX`, and any others are cut short there.
+    pub fn seed_ranges(&self) -> IntervalSet<usize> {
+        arr_chunks(self.seeds.iter().cloned())
+            .map(|[start, length]| {
+                Interval::checked_with_len(start, length)
+                    .unwrap_or(Interval::new(start, usize::MAX))
+            })
+            .collect()
+    }
+
+    pub fn locations_seed_ranges(&self) -> IntervalSet<usize> {
+        self.convert_seed_ranges(&self.seed_ranges())
+    }
+
+    /// The lowest location for any of `seeds`. Segments preserve order, so
+    /// only the first seed in each piece of `seeds` cut up by the composed
+    /// segments needs converting.
+    pub fn lowest_location(&self, seeds: &IntervalSet<usize>) -> Option<usize> {
+        let moved = self.composed.segments().iter().filter_map(|segment| {
+            let first = seeds.intersection(&segment.src.into()).min()?;
+            segment.convert(first)
+        });
+        let unmoved = seeds.difference(&self.composed.domain()).min();
+
+        moved.chain(unmoved).min()
+    }
+
+    /// Every seed planted at `location`. Intervals are half open, so none can
+    /// hold `usize::MAX` and it has no seeds.
+    pub fn seeds_for_location(&self, location: usize) -> IntervalSet<usize> {
+        match Interval::checked_with_len(location, 1) {
+            Some(location) => self.composed.preimage_interval(location),
+            None => IntervalSet::new(),
+        }
+    }
+
+    /// Every seed planted somewhere in `locations`.
+    pub fn seeds_for_locations(&self, locations: &IntervalSet<usize>) -> IntervalSet<usize> {
+        self.composed.preimage_set(locations)
+    }
+
+    /// The lowest location for any of `seeds`, found by walking up through
+    /// the locations until one comes from a seed in `seeds`. Which seeds
+    /// reach a location only changes at the edge of a segment or its image,
+    /// so the walk goes a whole stretch between those edges at a time.
+    pub fn lowest_location_reverse(&self, seeds: &IntervalSet<usize>) -> Option<usize> {
+        let mut edges: Vec<usize> = self
+            .composed
+            .segments()
+            .iter()
+            .flat_map(|s| [s.src.start, s.src.end, s.image().start, s.image().end])
+            .chain([0, usize::MAX])
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        edges.windows(2).find_map(|stretch| {
+            let locations = Interval::new(stretch[0], stretch[1]).into();
+            let planted = self.seeds_for_locations(&locations).intersection(seeds);
+            self.convert_seed_ranges(&planted).min()
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Map {
+    source: String,
+    destination: String,
+    map: PiecewiseMap<usize>,
+    /// Every value given a range in the input, including ones that map to
+    /// themselves and so don't appear in `map`.
+    covered: IntervalSet<usize>,
+}
+
+impl Map {
+    pub fn new(source: String, destination: String, ranges: Vec<Segment<usize>>) -> Self {
+        Self {
+            source,
+            destination,
+            covered: ranges.iter().map(|range| range.src).collect(),
+            map: PiecewiseMap::new(ranges),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+
+    /// The values between the lowest and highest ranges which no range
+    /// covers, and so pass through unchanged.
+    pub fn gaps(&self) -> IntervalSet<usize> {
+        match (
+            self.covered.intervals().first(),
+            self.covered.intervals().last(),
+        ) {
+            (Some(first), Some(last)) => {
+                IntervalSet::from(Interval::new(first.start, last.end)).difference(&self.covered)
+            }
+            _ => IntervalSet::new(),
+        }
+    }
+
+    pub fn convert(&self, src: usize) -> usize {
+        self.map.convert(src)
+    }
+
+    pub fn convert_set(&self, srcs: &IntervalSet<usize>) -> IntervalSet<usize> {
+        self.map.convert_set(srcs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_range_convert() {
+        let range = Segment::new(98, 50, 2);
+        assert_eq!(range.convert(97), None);
+        assert_eq!(range.convert(98), Some(50));
+        assert_eq!(range.convert(99), Some(51));
+        assert_eq!(range.convert(100), None);
+    }
+
+    #[test]
+    fn test_empty_seed_range() {
+        let almanac =
+            input::parse("seeds: 79 0 55 13\n\nseed-to-location map:\n50 98 2\n").unwrap();
+        assert_eq!(almanac.seed_ranges().intervals(), [Interval::new(55, 68)]);
+    }
+
+    #[test]
+    fn test_validation() {
+        let error = |input: &str| match input::parse(input).unwrap_err() {
+            Error::Parse { line, message, .. } => (line, message),
+            e => panic!("{e}"),
+        };
+
+        let (line, message) = error("seeds: 1\n\nseed-to-soil map:\n1 2 3\n");
+        assert_eq!(line, 5);
+        assert!(message.contains("`location`"), "{message}");
+
+        let (line, message) =
+            error("seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nwater-to-location map:\n1 2 3\n");
+        assert_eq!(line, 6);
+        assert!(message.contains("from `soil`"), "{message}");
+
+        let (line, message) = error("seeds: 1\n\nseed-location map:\n1 2 3\n");
+        assert_eq!(line, 3);
+        assert!(message.contains("source-to-destination"), "{message}");
+
+        let (line, message) = error("seeds: 1\n\nseed-to-location map:\n0 10 5\n0 20 5\n0 14 2\n");
+        assert_eq!(line, 6);
+        assert!(
+            message.contains("[14, 16) overlaps range [10, 15)"),
+            "{message}"
+        );
+
+        let (line, message) =
+            error("seeds: 1 2\n\nseed-to-location map:\n0 18446744073709551615 5\n");
+        assert_eq!(line, 4);
+        assert!(message.contains("runs past"), "{message}");
+
+        let (line, message) =
+            error("seeds: 1 2\n\nseed-to-location map:\n18446744073709551614 0 5\n");
+        assert_eq!(line, 4);
+        assert!(message.contains("runs past"), "{message}");
+
+        let (line, message) =
+            error("seeds: 18446744073709551615 2\n\nseed-to-location map:\n0 0 5\n");
+        assert_eq!(line, 1);
+        assert!(message.contains("seed range from"), "{message}");
+    }
+
+    #[test]
+    fn test_gaps() {
+        let almanac =
+            input::parse("seeds: 1\n\nseed-to-location map:\n0 10 5\n30 30 5\n0 40 5\n").unwrap();
+        let map = &almanac.maps()[0];
+
+        assert_eq!(map.source(), "seed");
+        assert_eq!(map.destination(), "location");
+        assert_eq!(
+            map.gaps().intervals(),
+            [Interval::new(15, 30), Interval::new(35, 40)]
+        );
+    }
+
+    #[test]
+    fn test_between() {
+        let almanac =
+            input::parse(&crate::aoc::example::example_string("day5.txt").unwrap()).unwrap();
+
+        assert_eq!(
+            almanac.between("seed", "location").as_ref(),
+            Some(almanac.composed())
+        );
+        assert_eq!(almanac.between("location", "seed"), None);
+        assert_eq!(almanac.between("soil", "bananas"), None);
+
+        let humidity = almanac.between("seed", "humidity").unwrap();
+        let location = almanac.between("humidity", "location").unwrap();
+        assert_eq!(humidity.then(&location), *almanac.composed());
+        // Seed 79 goes through soil 81 and fertilizer 81 to water 81
+        assert_eq!(almanac.between("seed", "water").unwrap().convert(79), 81);
+    }
+
+    #[test]
+    fn test_composed() {
+        let almanac =
+            input::parse(&crate::aoc::example::example_string("day5.txt").unwrap()).unwrap();
+
+        for seed in 0..120 {
+            let stepwise = almanac.maps().iter().fold(seed, |x, map| map.convert(x));
+            assert_eq!(almanac.convert(seed), stepwise, "{seed}");
+        }
+
+        let seeds = almanac.seed_ranges();
+        let stepwise = almanac
+            .maps()
+            .iter()
+            .fold(seeds.clone(), |srcs, map| map.convert_set(&srcs));
+        assert_eq!(almanac.convert_seed_ranges(&seeds), stepwise);
+        assert_eq!(almanac.lowest_location(&seeds), stepwise.min());
+    }
+
+    #[test]
+    fn test_reverse() {
+        let almanac =
+            input::parse(&crate::aoc::example::example_string("day5.txt").unwrap()).unwrap();
+
+        for seed in 0..120 {
+            let location = almanac.convert(seed);
+            let seeds = almanac.seeds_for_location(location);
+            assert!(seeds.contains(seed), "{seed}");
+            assert!(seeds
+                .iter()
+                .all(|i| almanac.convert(i.start) == location && i.len() == 1));
+        }
+
+        let seeds = almanac.seed_ranges();
+        let locations = almanac.convert_seed_ranges(&seeds);
+        assert_eq!(
+            almanac.seeds_for_locations(&locations).intersection(&seeds),
+            seeds
+        );
+        assert_eq!(almanac.lowest_location_reverse(&seeds), Some(46));
+        assert_eq!(almanac.lowest_location_reverse(&IntervalSet::new()), None);
+    }
+}
+
+mod input {
+    use crate::aoc::parse::*;
+
+    use super::*;
+
+    /// The category seeds belong to, where the chain of maps starts.
+    const FIRST: &str = "seed";
+    /// The category the chain of maps has to end at.
+    const LAST: &str = "location";
+
+    /// A range as `[destination, source, length]`, along with its line of
+    /// the input.
+    type Range<'a> = (&'a str, [usize; 3]);
+
+    pub fn parse(input: &str) -> Result<Almanac> {
+        let (s, seeds) = nom::sequence::preceded(
+            nom::bytes::complete::tag("seeds: "),
+            nom::multi::separated_list1(
+                nom::character::complete::space1,
+                nom::combinator::consumed(parse_number::<usize>),
+            ),
+        )(input)
+        .map_err(|e| nom_error(input, e))?;
+
+        // Part 2 reads the seeds as ranges, which have to end somewhere
+        for pair in seeds.chunks_exact(2) {
+            let [(_, start), (text, length)] = pair else {
+                unreachable!()
+            };
+            if Interval::checked_with_len(*start, *length).is_none() {
+                return Err(Error::parse(
+                    input,
+                    text,
+                    format!("seed range from {start} runs past {}", usize::MAX),
+                ));
+            }
+        }
+        let seeds = seeds.into_iter().map(|(_, seed)| seed).collect();
+
+        let (_, raw) = nom::sequence::terminated(
+            nom::multi::many1(parse_map),
+            nom::sequence::pair(nom::character::complete::multispace0, nom::combinator::eof),
+        )(s)
+        .map_err(|e| nom_error(input, e))?;
+
+        let mut maps: Vec<Map> = Vec::with_capacity(raw.len());
+        for (name, ranges) in raw {
+            let map = build_map(input, name, ranges)?;
+
+            let expected = maps.last().map_or(FIRST, |last| last.destination());
+            if map.source() != expected {
+                return Err(Error::parse(
+                    input,
+                    name,
+                    format!("expected a map from `{expected}`, the last map's destination"),
+                ));
+            }
+
+            maps.push(map);
+        }
+
+        let last = maps.last().unwrap().destination();
+        if last != LAST {
+            return Err(Error::parse(
+                input,
+                &input[input.len()..],
+                format!("maps end at `{last}` rather than `{LAST}`"),
+            ));
+        }
+
+        Ok(Almanac::new(seeds, maps))
+    }
+
+    /// Check a map's name and ranges, which must not overlap.
+    fn build_map(input: &str, name: &str, ranges: Vec<Range<'_>>) -> Result<Map> {
+        let (source, destination) = name.split_once("-to-").ok_or_else(|| {
+            Error::parse(
+                input,
+                name,
+                "map name should look like `source-to-destination`",
+            )
+        })?;
+
+        let mut ranges = ranges
+            .into_iter()
+            .map(|(line, [dst, src, length])| {
+                // Where the range maps to has to fit as well as where it's from
+                match Interval::checked_with_len(dst, length)
+                    .and(Interval::checked_with_len(src, length))
+                {
+                    Some(_) => Ok((line, Segment::new(src, dst, length))),
+                    None => Err(Error::parse(
+                        input,
+                        line,
+                        format!("range of length {length} runs past {}", usize::MAX),
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        ranges.retain(|(_, range)| !range.src.is_empty());
+        ranges.sort_by_key(|(_, range)| range.src.start);
+        for pair in ranges.windows(2) {
+            let [(_, a), (line, b)] = pair else {
+                unreachable!()
+            };
+            if a.src.overlaps(&b.src) {
+                return Err(Error::parse(
+                    input,
+                    line,
+                    format!("range {} overlaps range {} of the same map", b.src, a.src),
+                ));
+            }
+        }
+
+        Ok(Map::new(
+            source.to_owned(),
+            destination.to_owned(),
+            ranges.into_iter().map(|(_, range)| range).collect(),
+        ))
+    }
+
+    fn parse_range(s: &str) -> nom::IResult<&str, Range<'_>> {
+        let (s, (line, (dst, _, src, _, length))) =
+            nom::combinator::consumed(nom::sequence::tuple((
+                parse_number::<usize>,
+                nom::character::complete::space1,
+                parse_number::<usize>,
+                nom::character::complete::space1,
+                parse_number::<usize>,
+            )))(s)?;
+
+        Ok((s, (line, [dst, src, length])))
+    }
+
+    fn parse_map(s: &str) -> nom::IResult<&str, (&str, Vec<Range<'_>>)> {
+        const MAP_HEADER_END: &str = " map:\n";
+        let (s, name) = nom::bytes::complete::take_until1(MAP_HEADER_END)(s)?;
+        let name = name.trim();
+        let (s, ranges) = nom::sequence::preceded(
+            nom::bytes::complete::tag(MAP_HEADER_END),
+            nom::multi::separated_list1(nom::character::complete::char('\n'), parse_range),
+        )(s)?;
+
+        Ok((s, (name, ranges)))
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(almanac: &Almanac) -> Result<usize> {
+        almanac
+            .locations()
+            .iter()
+            .cloned()
+            .min()
+            .ok_or_else(|| Error::Unsolvable("no seeds to plant".to_owned()))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let almanac =
+                input::parse(&crate::aoc::example::example_string("day5.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&almanac).unwrap(), 35);
+        }
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(almanac: &Almanac) -> Result<usize> {
+        almanac
+            .lowest_location(&almanac.seed_ranges())
+            .ok_or_else(|| Error::Unsolvable("no seed ranges to plant".to_owned()))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let almanac =
+                input::parse(&crate::aoc::example::example_string("day5.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&almanac).unwrap(), 46);
+        }
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        part1::calculate(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2::calculate(input)
+    }
+}
@@ -0,0 +1,787 @@
+use crate::{
+    aoc::{parse::parse_num, Error, Result, Solution},
+    bignum::BigUint,
+    interval::Interval,
+    quant::isqrt,
+};
+
+#[derive(Debug)]
+pub struct Race {
+    time: usize,
+    distance: usize,
+}
+
+impl Race {
+    pub fn new(time: usize, distance: usize) -> Self {
+        Self { time, distance }
+    }
+
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+
+    pub fn calculate_distance(&self, held_time: usize) -> usize {
+        if held_time >= self.time {
+            0
+        } else {
+            (self.time - held_time) * held_time
+        }
+    }
+
+    /// Every hold time that beats the record, worked out exactly by
+    /// [`Physics::winning_holds`] with the puzzle's physics.
+    pub fn winning_holds(&self) -> Interval<usize> {
+        Physics::default().winning_holds(self)
+    }
+
+    pub fn record_count(&self) -> usize {
+        self.winning_holds().len()
+    }
+}
+
+/// How the boats move. Each millisecond the button is held adds
+/// `acceleration` to the boat's starting speed, up to its top speed if it
+/// has one, and it keeps that speed for the rest of the race. The puzzle's
+/// boats are `Physics::default()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Physics {
+    pub acceleration: usize,
+    pub start_speed: usize,
+    pub max_speed: Option<usize>,
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self {
+            acceleration: 1,
+            start_speed: 0,
+            max_speed: None,
+        }
+    }
+}
+
+impl Physics {
+    /// The speed after holding the button for `held`. It saturates instead
+    /// of overflowing, which is still far faster than any record needs.
+    pub fn speed(&self, held: usize) -> u128 {
+        let speed = (self.acceleration as u128)
+            .saturating_mul(held as u128)
+            .saturating_add(self.start_speed as u128);
+        self.max_speed.map_or(speed, |max| speed.min(max as u128))
+    }
+
+    /// How far the boat gets in a race of `time`, saturating like
+    /// [`Physics::speed`] so comparisons with a record stay exact.
+    pub fn distance(&self, time: usize, held: usize) -> u128 {
+        match time.checked_sub(held) {
+            Some(moving) => self.speed(held).saturating_mul(moving as u128),
+            None => 0,
+        }
+    }
+
+    pub fn wins(&self, race: &Race, held: usize) -> bool {
+        self.distance(race.time, held) > race.distance as u128
+    }
+
+    /// Every hold time that beats the record, worked out exactly. The
+    /// quadratic is solved in `u128` with [`isqrt`] when its discriminant
+    /// fits, as it always does with the puzzle's physics, and falls back to
+    /// big integers, as [`Physics::big_winning_holds`] does, when it doesn't.
+    pub fn winning_holds(&self, race: &Race) -> Interval<usize> {
+        if self.acceleration == 0 || self.max_speed.is_some_and(|max| self.start_speed >= max) {
+            let speed = self
+                .max_speed
+                .map_or(self.start_speed, |max| max.min(self.start_speed));
+            return Self::constant_speed_holds(race, speed);
+        }
+
+        let accelerating = self.accelerating_holds(race).unwrap_or_else(|| {
+            let fit = |hold: BigUint| {
+                let hold = hold.to_u128().and_then(|hold| usize::try_from(hold).ok());
+                hold.expect("winning holds are no longer than the race")
+            };
+            match self.big_accelerating_holds(&race.into()) {
+                // Holding for the whole race never wins, so last + 1 can't overflow
+                Some((first, last)) => Interval::new(fit(first), fit(last) + 1),
+                None => Interval::default(),
+            }
+        });
+        match self.max_speed {
+            Some(max) => accelerating
+                .intersection(&Self::constant_speed_holds(race, max))
+                .unwrap_or_default(),
+            None => accelerating,
+        }
+    }
+
+    /// The first and last hold times that beat the record, if any do, for a
+    /// race of any size.
+    ///
+    /// Below the top speed the distance is the quadratic
+    /// `(start + acceleration * h) * (time - h)`, and it never exceeds the
+    /// line `max_speed * (time - h)`. The distance is always the smaller of
+    /// the two, so a hold has to beat the record on both.
+    pub fn big_winning_holds(&self, race: &BigRace) -> Option<(BigUint, BigUint)> {
+        let start = BigUint::from(self.start_speed);
+        let max = self.max_speed.map(BigUint::from);
+
+        if self.acceleration == 0 || max.as_ref().is_some_and(|max| start >= *max) {
+            let speed = max.map_or(start.clone(), |max| max.min(start));
+            return Self::big_constant_speed_holds(race, &speed);
+        }
+
+        let (first, last) = self.big_accelerating_holds(race)?;
+        match max {
+            Some(max) => {
+                let (_, last_capped) = Self::big_constant_speed_holds(race, &max)?;
+                let last = last.min(last_capped);
+                (first <= last).then_some((first, last))
+            }
+            None => Some((first, last)),
+        }
+    }
+
+    pub fn big_record_count(&self, race: &BigRace) -> BigUint {
+        match self.big_winning_holds(race) {
+            Some((first, last)) => &(&last - &first) + &BigUint::one(),
+            None => BigUint::zero(),
+        }
+    }
+
+    /// The holds that win at a constant `speed`, those that leave more than
+    /// `distance / speed` to cover the record.
+    fn constant_speed_holds(race: &Race, speed: usize) -> Interval<usize> {
+        match speed {
+            0 => Interval::default(),
+            _ => Interval::new(0, race.time - (race.distance / speed).min(race.time)),
+        }
+    }
+
+    /// [`Physics::constant_speed_holds`] for a race of any size.
+    fn big_constant_speed_holds(race: &BigRace, speed: &BigUint) -> Option<(BigUint, BigUint)> {
+        if speed.is_zero() {
+            return None;
+        }
+
+        let needed = &race.distance.div_rem(speed).0 + &BigUint::one();
+        Some((BigUint::zero(), race.time.checked_sub(&needed)?))
+    }
+
+    /// The holds that win if the boat never reaches its top speed: those
+    /// between the roots of `a * h^2 - b * h + c`, where `b` is
+    /// `a * time - start` and `c` is `distance - start * time`. Either can
+    /// be negative, so the roots are estimated on their magnitudes and then
+    /// stepped into place. `None` if the discriminant doesn't fit in a
+    /// `u128`.
+    fn accelerating_holds(&self, race: &Race) -> Option<Interval<usize>> {
+        let uncapped = Physics {
+            max_speed: None,
+            ..*self
+        };
+        let wins = |held| uncapped.wins(race, held);
+
+        let [a, start, time, distance] = [
+            self.acceleration,
+            self.start_speed,
+            race.time,
+            race.distance,
+        ]
+        .map(|n| n as u128);
+        let at = a * time;
+        let (b, b_negative) = match at.checked_sub(start) {
+            Some(b) => (b, false),
+            None => (start - at, true),
+        };
+
+        let four_a = a << 2;
+        let positive = b
+            .checked_mul(b)?
+            .checked_add(four_a.checked_mul(start * time)?)?;
+        let Some(discriminant) = positive.checked_sub(four_a * distance) else {
+            return Some(Interval::default());
+        };
+        let root = isqrt(discriminant);
+
+        // Each root to within one, with the negative ones as zero. The square
+        // of b fits, so b + root can't overflow.
+        let to_hold = |numerator: u128| (numerator / (a << 1)).min(time) as usize;
+        let (lower, upper) = match b_negative {
+            false => (b.checked_sub(root), Some(b + root)),
+            true => (None, root.checked_sub(b)),
+        };
+        let Some(upper) = upper else {
+            return Some(Interval::default());
+        };
+        let (mut first, mut last) = (lower.map_or(0, to_hold), to_hold(upper));
+
+        while first > 0 && wins(first - 1) {
+            first -= 1;
+        }
+        while last < race.time && wins(last + 1) {
+            last += 1;
+        }
+        while first <= last && !wins(first) {
+            first += 1;
+        }
+        while last > first && !wins(last) {
+            last -= 1;
+        }
+
+        Some(match first <= last {
+            // Holding for the whole race never wins, so last + 1 can't overflow
+            true => Interval::new(first, last + 1),
+            false => Interval::default(),
+        })
+    }
+
+    /// [`Physics::accelerating_holds`] for a race of any size.
+    fn big_accelerating_holds(&self, race: &BigRace) -> Option<(BigUint, BigUint)> {
+        let [a, start] = [self.acceleration, self.start_speed].map(BigUint::from);
+        let (time, distance, one) = (&race.time, &race.distance, BigUint::one());
+        let wins = |held: &BigUint| match time.checked_sub(held) {
+            Some(moving) => &(&start + &(&a * held)) * &moving > *distance,
+            None => false,
+        };
+
+        let at = &a * time;
+        let (b, b_negative) = match at.checked_sub(&start) {
+            Some(b) => (b, false),
+            None => (&start - &at, true),
+        };
+
+        let four_a = &a << 2;
+        let discriminant =
+            (&(&b * &b) + &(&four_a * &(&start * time))).checked_sub(&(&four_a * distance))?;
+        let root = discriminant.isqrt();
+
+        // Each root to within one, with the negative ones as zero
+        let two_a = &a << 1;
+        let to_hold = |numerator: BigUint| numerator.div_rem(&two_a).0.min(time.clone());
+        let (lower, upper) = match b_negative {
+            false => (b.checked_sub(&root), Some(&b + &root)),
+            true => (None, root.checked_sub(&b)),
+        };
+        let (mut first, mut last) = (lower.map_or(BigUint::zero(), to_hold), to_hold(upper?));
+
+        while !first.is_zero() && wins(&(&first - &one)) {
+            first = &first - &one;
+        }
+        while last < *time && wins(&(&last + &one)) {
+            last = &last + &one;
+        }
+        while first <= last && !wins(&first) {
+            first = &first + &one;
+        }
+        while last > first && !wins(&last) {
+            last = &last - &one;
+        }
+
+        (first <= last).then_some((first, last))
+    }
+
+    /// The shortest race with a record of `distance` that can be won in at
+    /// least `strategies` ways, if there is one. Longer races only ever add
+    /// winning holds, so this doubles the time until there are enough and
+    /// then bisects.
+    pub fn min_race_time(&self, distance: usize, strategies: usize) -> Option<usize> {
+        let enough = |time| self.winning_holds(&Race { time, distance }).len() >= strategies;
+
+        if enough(0) {
+            return Some(0);
+        }
+        let mut high = 1usize;
+        while !enough(high) {
+            if high == usize::MAX {
+                return None;
+            }
+            high = high.saturating_mul(2);
+        }
+
+        let mut low = high / 2;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            match enough(mid) {
+                true => high = mid,
+                false => low = mid,
+            }
+        }
+
+        Some(high)
+    }
+}
+
+/// A race whose numbers may not fit in a machine word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigRace {
+    time: BigUint,
+    distance: BigUint,
+}
+
+impl BigRace {
+    pub fn new(time: BigUint, distance: BigUint) -> Self {
+        Self { time, distance }
+    }
+
+    pub fn time(&self) -> &BigUint {
+        &self.time
+    }
+
+    pub fn distance(&self) -> &BigUint {
+        &self.distance
+    }
+
+    pub fn calculate_distance(&self, held_time: &BigUint) -> BigUint {
+        match self.time.checked_sub(held_time) {
+            Some(moving) => &moving * held_time,
+            None => BigUint::zero(),
+        }
+    }
+
+    /// The first and last hold times that beat the record, if any do, with
+    /// the puzzle's physics.
+    pub fn winning_holds(&self) -> Option<(BigUint, BigUint)> {
+        Physics::default().big_winning_holds(self)
+    }
+
+    pub fn record_count(&self) -> BigUint {
+        Physics::default().big_record_count(self)
+    }
+}
+
+impl From<&Race> for BigRace {
+    fn from(race: &Race) -> Self {
+        Self::new(race.time.into(), race.distance.into())
+    }
+}
+
+pub struct Races(Vec<Race>);
+
+impl Races {
+    pub fn races(&self) -> &[Race] {
+        &self.0
+    }
+
+    /// The product of the ways to win each race, which soon outgrows a
+    /// `usize` as races are added.
+    pub fn ways_to_win(&self, physics: &Physics) -> BigUint {
+        self.0.iter().fold(BigUint::one(), |acc, race| {
+            &acc * &BigUint::from(physics.winning_holds(race).len())
+        })
+    }
+
+    /// One field of every race with their digits run together, which soon
+    /// stops fitting in a `usize` as races are added.
+    fn smushed(&self, field: impl Fn(&Race) -> usize) -> BigUint {
+        self.0
+            .iter()
+            .map(field)
+            .fold(BigUint::zero(), |acc, value| {
+                let digits = value.checked_ilog10().unwrap_or(0) + 1;
+                &(&acc * &BigUint::from(10u128.pow(digits))) + &BigUint::from(value)
+            })
+    }
+
+    pub fn smushed_time(&self) -> BigUint {
+        self.smushed(|race| race.time)
+    }
+
+    pub fn smushed_distance(&self) -> BigUint {
+        self.smushed(|race| race.distance)
+    }
+
+    pub fn smushed_race(&self) -> BigRace {
+        BigRace::new(self.smushed_time(), self.smushed_distance())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn brute_force(race: &Race) -> Interval<usize> {
+        let mut wins =
+            (0..=race.time).filter(|&hold| race.calculate_distance(hold) > race.distance);
+        match wins.next() {
+            Some(first) => Interval::new(first, wins.next_back().unwrap_or(first) + 1),
+            None => Interval::default(),
+        }
+    }
+
+    #[test]
+    fn test_small_races() {
+        for time in 0..60 {
+            for distance in 0..time * time / 4 + 3 {
+                let race = Race { time, distance };
+                let holds = race.winning_holds();
+                assert_eq!(holds, brute_force(&race), "{race:?}");
+
+                let big = BigRace::from(&race).winning_holds();
+                let big = big.map(|(first, last)| (first.to_u128(), last.to_u128()));
+                let expected = (!holds.is_empty())
+                    .then(|| (Some(holds.start as u128), Some(holds.end as u128 - 1)));
+                assert_eq!(big, expected, "{race:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_races() {
+        // xorshift64, so the races are the same every run
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..10_000 {
+            let time = (next() >> (next() % 64)) as usize;
+            let hold = next() as usize % (time + 1);
+            // A record tied by holding for `hold`, so it and its mirror lose
+            let Some(distance) = hold.checked_mul(time - hold) else {
+                continue;
+            };
+            let race = Race { time, distance };
+            let holds = race.winning_holds();
+
+            if !holds.is_empty() {
+                assert!(race.calculate_distance(holds.start) > distance, "{race:?}");
+                assert!(
+                    race.calculate_distance(holds.end - 1) > distance,
+                    "{race:?}"
+                );
+                assert_eq!(holds.start + holds.end - 1, time, "{race:?}");
+            }
+            if holds.start > 0 {
+                assert!(
+                    race.calculate_distance(holds.start - 1) <= distance,
+                    "{race:?}"
+                );
+            }
+            assert!(
+                !holds.contains(hold) && !holds.contains(time - hold),
+                "{race:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_huge_race() {
+        let race = Race {
+            time: usize::MAX,
+            distance: usize::MAX,
+        };
+        let holds = race.winning_holds();
+        assert_eq!((holds.start, holds.end), (2, usize::MAX - 1));
+    }
+
+    #[test]
+    fn test_big_physics() {
+        let smushed = many_races().smushed_race();
+        let time = smushed.time().clone();
+        let one = BigUint::one();
+
+        let accelerating = Physics {
+            acceleration: 3,
+            start_speed: 5,
+            max_speed: None,
+        };
+        let capped = Physics {
+            acceleration: 2,
+            start_speed: 0,
+            max_speed: Some(usize::MAX),
+        };
+        // Even flat out the whole race, the capped boat can't beat this
+        assert_eq!(capped.big_winning_holds(&smushed), None);
+
+        // Records the capped boat can beat, by reaching top speed early
+        let reachable = BigRace::new(time.clone(), &time * &BigUint::from(1u64 << 40));
+
+        for (physics, race) in [
+            (Physics::default(), &smushed),
+            (accelerating, &smushed),
+            (capped, &reachable),
+        ] {
+            let travels = |held: &BigUint| match race.time().checked_sub(held) {
+                Some(moving) => {
+                    let speed = &BigUint::from(physics.start_speed)
+                        + &(&BigUint::from(physics.acceleration) * held);
+                    let speed = physics
+                        .max_speed
+                        .map_or(speed.clone(), |max| speed.min(max.into()));
+                    &speed * &moving
+                }
+                None => BigUint::zero(),
+            };
+
+            let (first, last) = physics.big_winning_holds(race).unwrap();
+            assert!(travels(&first) > *race.distance(), "{physics:?}");
+            assert!(travels(&(&first - &one)) <= *race.distance(), "{physics:?}");
+            assert!(travels(&last) > *race.distance(), "{physics:?}");
+            assert!(travels(&(&last + &one)) <= *race.distance(), "{physics:?}");
+            assert_eq!(
+                physics.big_record_count(race),
+                &(&last - &first) + &one,
+                "{physics:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_physics() {
+        let mut physics_seen = 0;
+        for acceleration in 0..4 {
+            for start_speed in [0, 1, 3, 7] {
+                for max_speed in [None, Some(0), Some(2), Some(5), Some(9)] {
+                    let physics = Physics {
+                        acceleration,
+                        start_speed,
+                        max_speed,
+                    };
+                    physics_seen += 1;
+
+                    for time in 0..30 {
+                        for distance in 0..60 {
+                            let race = Race { time, distance };
+                            let mut wins = (0..=time).filter(|&hold| physics.wins(&race, hold));
+                            let expected = match wins.next() {
+                                Some(first) => {
+                                    Interval::new(first, wins.next_back().unwrap_or(first) + 1)
+                                }
+                                None => Interval::default(),
+                            };
+                            assert_eq!(
+                                physics.winning_holds(&race),
+                                expected,
+                                "{physics:?} {race:?}"
+                            );
+
+                            let big = physics.big_winning_holds(&(&race).into());
+                            let big = big.map(|(first, last)| (first.to_u128(), last.to_u128()));
+                            let expected = (!expected.is_empty()).then(|| {
+                                (Some(expected.start as u128), Some(expected.end as u128 - 1))
+                            });
+                            assert_eq!(big, expected, "{physics:?} {race:?}");
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(physics_seen, 80);
+
+        // A start speed past a * time makes b negative
+        let physics = Physics {
+            acceleration: 1,
+            start_speed: 100,
+            max_speed: None,
+        };
+        let race = Race::new(10, 500);
+        assert_eq!(physics.winning_holds(&race), Interval::new(0, 6));
+
+        // Huge speeds saturate without breaking exactness
+        let physics = Physics {
+            acceleration: usize::MAX,
+            start_speed: usize::MAX,
+            max_speed: None,
+        };
+        let race = Race::new(usize::MAX, usize::MAX);
+        assert_eq!(physics.winning_holds(&race), Interval::new(0, usize::MAX));
+    }
+
+    #[test]
+    fn test_min_race_time() {
+        let physics = Physics::default();
+        assert_eq!(physics.min_race_time(9, 0), Some(0));
+        assert_eq!(physics.min_race_time(9, 4), Some(7));
+        assert_eq!(physics.min_race_time(200, 9), Some(30));
+
+        let physics = Physics {
+            acceleration: 2,
+            start_speed: 1,
+            max_speed: Some(6),
+        };
+        for distance in 0..50 {
+            for strategies in 0..20 {
+                let expected = (0..200).find(|&time| {
+                    physics.winning_holds(&Race { time, distance }).len() >= strategies
+                });
+                assert_eq!(
+                    physics.min_race_time(distance, strategies),
+                    expected,
+                    "{distance} {strategies}"
+                );
+            }
+        }
+
+        let stuck = Physics {
+            acceleration: 0,
+            start_speed: 0,
+            max_speed: None,
+        };
+        assert_eq!(stuck.min_race_time(10, 1), None);
+
+        let race = Race::new(usize::MAX, usize::MAX);
+        let strategies = race.winning_holds().len();
+        assert_eq!(
+            Physics::default().min_race_time(usize::MAX, strategies),
+            Some(usize::MAX)
+        );
+    }
+
+    /// Enough races that neither the smushed race nor the product of the
+    /// ways to win them fits in a machine word.
+    fn many_races() -> Races {
+        Races(
+            (1..=30)
+                .map(|i| Race {
+                    time: 1_000_000 + i * 7919,
+                    distance: 200_000_000_000 + i * 104_729,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_many_races() {
+        let races = many_races();
+        let product = part1::calculate(&races);
+        assert!(product.bits() > 500);
+
+        // Every race's count is a factor, with nothing left over
+        let rest = races.races().iter().fold(product, |product, race| {
+            let (quotient, rem) = product.div_rem(&race.record_count().into());
+            assert!(rem.is_zero(), "{race:?}");
+            quotient
+        });
+        assert_eq!(rest, BigUint::one());
+    }
+
+    #[test]
+    fn test_smushed_race() {
+        let races = many_races();
+        let race = races.smushed_race();
+        assert_eq!(race.time().to_string().len(), 30 * 7);
+        assert!(race.time().to_string().starts_with("10079191015838"));
+
+        let (first, last) = race.winning_holds().unwrap();
+        let one = BigUint::one();
+        assert!(race.calculate_distance(&first) > *race.distance());
+        assert!(race.calculate_distance(&(&first - &one)) <= *race.distance());
+        assert!(race.calculate_distance(&last) > *race.distance());
+        assert!(race.calculate_distance(&(&last + &one)) <= *race.distance());
+        assert_eq!(&first + &last, *race.time());
+        assert_eq!(race.record_count(), &(&last - &first) + &one);
+
+        // A record nobody can beat
+        let distance = &(race.time() * race.time()) >> 2;
+        assert_eq!(
+            BigRace::new(race.time().clone(), distance).record_count(),
+            BigUint::zero()
+        );
+    }
+}
+
+fn parse_row(input: &str, line: Option<&str>, label: &str) -> Result<Vec<usize>> {
+    let line = line.unwrap_or(&input[input.len()..]);
+    let values = line
+        .strip_prefix(label)
+        .ok_or_else(|| Error::parse(input, line, format!("expected `{label}`")))?;
+
+    values
+        .split_ascii_whitespace()
+        .map(|value| parse_num(input, value))
+        .collect()
+}
+
+fn parse_input(s: &str) -> Result<Races> {
+    let mut lines = s.lines();
+    let times = parse_row(s, lines.next(), "Time:")?;
+
+    let dists_line = lines.next();
+    let dists = parse_row(s, dists_line, "Distance:")?;
+
+    if times.len() != dists.len() {
+        return Err(Error::parse(
+            s,
+            dists_line.unwrap_or_default(),
+            format!("expected {} distances, found {}", times.len(), dists.len()),
+        ));
+    }
+
+    Ok(Races(
+        times
+            .iter()
+            .zip(dists.iter())
+            .map(|(&time, &distance)| Race { time, distance })
+            .collect(),
+    ))
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(races: &Races) -> BigUint {
+        races.ways_to_win(&Physics::default())
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let races =
+                parse_input(&crate::aoc::example::example_string("day6.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&races), BigUint::from(288u32));
+        }
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(races: &Races) -> BigUint {
+        races.smushed_race().record_count()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let races =
+                parse_input(&crate::aoc::example::example_string("day6.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&races), BigUint::from(71503u32));
+        }
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Races;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1::calculate(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2::calculate(input))
+    }
+}
@@ -0,0 +1,644 @@
+use std::{fmt::Display, ops::Deref, path::Path, str::FromStr};
+
+use crate::aoc::{
+    error::read_input,
+    parse::{parse_lines, parse_num, parse_setting, Setting, Value},
+    Error, Result, Solution,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Hand([u8; 5]);
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0 {
+            write!(f, "{}", c as char)?
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandType {
+    FiveOfAKind = 0, // 1 distinct, _
+    FourOfAKind,     // 2 distinct, max freq 4
+    FullHouse,       // 2 distinct, max freq 3
+    ThreeOfAKind,    // 3 distinct, max freq 3
+    TwoPair,         // 3 distinct, max freq 2
+    OnePair,         // 4 distinct, _
+    High,            // 5 distinct, _
+}
+
+impl HandType {
+    pub const fn rank(&self) -> u8 {
+        (HandType::High as u8) - (*self as u8)
+    }
+}
+
+impl Display for HandType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            HandType::FiveOfAKind => "five of a kind",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::FullHouse => "full house",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::TwoPair => "two pair",
+            HandType::OnePair => "one pair",
+            HandType::High => "high card",
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Round {
+    hand: Hand,
+    bid: usize,
+}
+
+impl Round {
+    pub fn hand(&self) -> Hand {
+        self.hand
+    }
+
+    pub fn bid(&self) -> usize {
+        self.bid
+    }
+}
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+impl FromStr for Round {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (hand, bid) = s
+            .split_once(' ')
+            .ok_or_else(|| Error::parse(s, s, "expected `<hand> <bid>`"))?;
+
+        if let Some(i) = hand.bytes().position(|c| !CARDS.contains(&c)) {
+            return Err(Error::parse(s, &hand[i..], "unknown card"));
+        }
+
+        Ok(Self {
+            hand: Hand(
+                hand.as_bytes()
+                    .try_into()
+                    .map_err(|_| Error::parse(s, hand, "expected a hand of 5 cards"))?,
+            ),
+            bid: parse_num(s, bid)?,
+        })
+    }
+}
+
+pub struct Rounds(Vec<Round>);
+
+impl FromStr for Rounds {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self(parse_lines(s, str::parse)?))
+    }
+}
+
+impl Deref for Rounds {
+    type Target = Vec<Round>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// How hands are sorted into types before their cards are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classifier {
+    /// Five of a kind down to high card, from how many of each card there are
+    Standard,
+    /// No hand types, so only the cards themselves count
+    CardsOnly,
+}
+
+impl FromStr for Classifier {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "standard" => Ok(Classifier::Standard),
+            "cards-only" => Ok(Classifier::CardsOnly),
+            _ => Err(format!(
+                "unknown classifier `{s}`, expected `standard` or `cards-only`"
+            )),
+        }
+    }
+}
+
+/// A set of Camel Cards rules, which can be loaded from a small `key = value`
+/// file like:
+///
+/// ```text
+/// # Jokers are wild but keep their place between T and Q
+/// order = "23456789TJQKA"
+/// wildcards = "J"
+/// classifier = "standard"
+/// ```
+///
+/// Every key is optional and defaults to the part 1 rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Every card, weakest first.
+    order: Vec<u8>,
+    /// Cards that stand in for whichever card makes the best hand.
+    wildcards: Vec<u8>,
+    classifier: Classifier,
+    /// Each card's place in `order`, looked up by the card itself.
+    strength: [u8; 256],
+    /// A bit set at the strength of each wildcard.
+    wild: u16,
+}
+
+impl Rules {
+    /// Names of the rules that [`Rules::builtin`] knows.
+    pub const BUILTIN: &'static [&'static str] = &["standard", "jokers", "jokers-high"];
+
+    /// Rules from an order and wildcards which have passed [`Rules::check`].
+    fn new(order: &[u8], wildcards: &[u8], classifier: Classifier) -> Self {
+        let mut strength = [0; 256];
+        for (i, &card) in order.iter().enumerate() {
+            strength[card as usize] = i as u8;
+        }
+
+        Self {
+            order: order.to_vec(),
+            wildcards: wildcards.to_vec(),
+            classifier,
+            strength,
+            wild: wildcards
+                .iter()
+                .fold(0, |wild, &c| wild | 1 << strength[c as usize]),
+        }
+    }
+
+    pub fn part1() -> Self {
+        Self::new(CARDS, b"", Classifier::Standard)
+    }
+
+    pub fn part2() -> Self {
+        Self::new(b"J23456789TQKA", b"J", Classifier::Standard)
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Self::part1()),
+            "jokers" => Some(Self::part2()),
+            "jokers-high" => Some(Self::new(CARDS, b"J", Classifier::Standard)),
+            _ => None,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&read_input(path)?)
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut order = CARDS;
+        let mut wildcards: &[u8] = b"";
+        let mut classifier = Classifier::Standard;
+
+        for line in input.lines() {
+            let (key, value) = match parse_setting(input, line)? {
+                None => continue,
+                Some(Setting::Section(name)) => {
+                    return Err(Error::parse(input, name, "expected `key = \"value\"`"))
+                }
+                Some(Setting::Entry(key, value)) => (key, value),
+            };
+            let text = match value {
+                Value::Quoted(text) => text,
+                Value::Bare(value) => {
+                    return Err(Error::parse(input, value, "expected a quoted string"))
+                }
+            };
+
+            match key {
+                "order" => order = text.as_bytes(),
+                "wildcards" => wildcards = text.as_bytes(),
+                "classifier" => {
+                    classifier = text.parse().map_err(|e| Error::parse(input, text, e))?
+                }
+                key => {
+                    return Err(Error::parse(
+                        input,
+                        key,
+                        "expected `order`, `wildcards` or `classifier`",
+                    ))
+                }
+            }
+
+            Self::check(order, wildcards).map_err(|e| Error::parse(input, text, e))?;
+        }
+
+        Ok(Self::new(order, wildcards, classifier))
+    }
+
+    /// Make sure every card is ranked exactly once and wildcards are cards.
+    fn check(order: &[u8], wildcards: &[u8]) -> std::result::Result<(), String> {
+        let mut sorted = order.to_vec();
+        sorted.sort_unstable();
+        let mut cards = CARDS.to_vec();
+        cards.sort_unstable();
+        if sorted != cards {
+            return Err(format!(
+                "order should have each of {} once",
+                String::from_utf8_lossy(CARDS)
+            ));
+        }
+
+        match wildcards.iter().find(|c| !CARDS.contains(c)) {
+            Some(&c) => Err(format!("unknown wildcard `{}`", c as char)),
+            None => Ok(()),
+        }
+    }
+
+    fn card_rank(&self, card: u8) -> u8 {
+        // Cards are checked when parsing, and the order when making rules
+        self.strength[card as usize]
+    }
+
+    fn hand_type(&self, hand: &Hand) -> HandType {
+        if self.classifier == Classifier::CardsOnly {
+            return HandType::High;
+        }
+
+        let mut counts = [0u8; CARDS.len()];
+        let mut wild = 0;
+        for card in hand.0 {
+            let strength = self.card_rank(card);
+            if self.wild & 1 << strength != 0 {
+                wild += 1;
+            } else {
+                counts[strength as usize] += 1;
+            }
+        }
+
+        // Wildcards do best joining whichever card there is most of
+        let distinct = counts.iter().filter(|&&n| n > 0).count().max(1);
+        let max_freq = counts.iter().copied().max().unwrap() + wild;
+
+        match (distinct, max_freq) {
+            (1, _) => HandType::FiveOfAKind,
+            (2, 4) => HandType::FourOfAKind,
+            (2, 3) => HandType::FullHouse,
+            (3, 3) => HandType::ThreeOfAKind,
+            (3, 2) => HandType::TwoPair,
+            (4, _) => HandType::OnePair,
+            (5, _) => HandType::High,
+            _ => unreachable!(),
+        }
+    }
+
+    /// A key ordering hands from weakest to strongest, with the hand type in
+    /// the top bits and then four bits for each card in turn.
+    fn sort_key(&self, hand: &Hand) -> u32 {
+        hand.0
+            .iter()
+            .fold(self.hand_type(hand).rank() as u32, |key, &card| {
+                key << 4 | self.card_rank(card) as u32
+            })
+    }
+
+    pub fn rank<'a>(&self, rounds: &'a Rounds) -> Vec<(usize, &'a Round)> {
+        let mut ranked: Vec<&Round> = rounds.0.iter().collect();
+
+        ranked.sort_by_cached_key(|round| self.sort_key(&round.hand));
+
+        ranked
+            .into_iter()
+            .enumerate()
+            .map(|(i, round)| (i + 1, round))
+            .collect()
+    }
+
+    /// The hand the wildcards in `hand` act as, if it has any. They all
+    /// become the card there is most of, or the strongest one on a tie.
+    pub fn substitution(&self, hand: &Hand) -> Option<Hand> {
+        let is_wild = |card: u8| self.wild & 1 << self.card_rank(card) != 0;
+        if self.classifier == Classifier::CardsOnly || !hand.0.iter().any(|&c| is_wild(c)) {
+            return None;
+        }
+
+        let target = hand
+            .0
+            .iter()
+            .copied()
+            .filter(|&c| !is_wild(c))
+            .max_by_key(|&c| {
+                (
+                    hand.0.iter().filter(|&&d| d == c).count(),
+                    self.card_rank(c),
+                )
+            })
+            .or_else(|| self.order.iter().copied().rev().find(|&c| !is_wild(c)))?;
+
+        Some(Hand(hand.0.map(|c| if is_wild(c) { target } else { c })))
+    }
+
+    /// Every round in rank order, with how it was classified.
+    pub fn explain<'a>(&self, rounds: &'a Rounds) -> Explanation<'a> {
+        Explanation(
+            self.rank(rounds)
+                .into_iter()
+                .map(|(rank, round)| Ranked {
+                    rank,
+                    round,
+                    hand_type: self.hand_type(&round.hand),
+                    substitution: self.substitution(&round.hand),
+                })
+                .collect(),
+        )
+    }
+
+    /// The total winnings of every round.
+    pub fn winnings(&self, rounds: &Rounds) -> usize {
+        self.rank(rounds)
+            .iter()
+            .map(|(i, round)| i * round.bid)
+            .sum()
+    }
+}
+
+/// A round's place in the ranking and why it got there.
+#[derive(Clone, Copy)]
+pub struct Ranked<'a> {
+    pub rank: usize,
+    pub round: &'a Round,
+    pub hand_type: HandType,
+    /// What any wildcards in the hand stood in for.
+    pub substitution: Option<Hand>,
+}
+
+impl Ranked<'_> {
+    pub fn winnings(&self) -> usize {
+        self.rank * self.round.bid
+    }
+}
+
+/// Every ranked round, shown as a table ending in the total winnings.
+pub struct Explanation<'a>(pub Vec<Ranked<'a>>);
+
+impl Display for Explanation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rank_width = self.0.len().to_string().len().max(4);
+        let bid_width = self
+            .0
+            .iter()
+            .map(|r| r.round.bid.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max(3);
+        let total: usize = self.0.iter().map(Ranked::winnings).sum();
+        let winnings_width = total.to_string().len().max(8);
+
+        writeln!(
+            f,
+            "{:>rank_width$}  Hand   {:>bid_width$}  {:<15}  As     {:>winnings_width$}",
+            "Rank", "Bid", "Type", "Winnings"
+        )?;
+        for ranked in &self.0 {
+            let substitution = ranked
+                .substitution
+                .map_or("-".to_owned(), |hand| hand.to_string());
+            writeln!(
+                f,
+                "{:>rank_width$}  {}  {:>bid_width$}  {:<15}  {substitution:<5}  {:>winnings_width$}",
+                ranked.rank,
+                ranked.round.hand,
+                ranked.round.bid,
+                ranked.hand_type.to_string(),
+                ranked.winnings(),
+            )?;
+        }
+        writeln!(f, "Total winnings: {total}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        let rounds =
+            parse_input(&crate::aoc::example::example_string("day7.txt").unwrap()).unwrap();
+
+        assert_eq!(Rules::builtin("standard"), Some(Rules::part1()));
+        assert_eq!(Rules::builtin("jokers"), Some(Rules::part2()));
+
+        // Jokers high still puts the example's three four of a kinds in the
+        // same order as part 2, since none of them start with a joker
+        let high = Rules::builtin("jokers-high").unwrap();
+        assert_eq!(high.hand_type(&Hand(*b"KTJJT")), HandType::FourOfAKind);
+        assert!(high.card_rank(b'J') > high.card_rank(b'T'));
+        assert_eq!(high.winnings(&rounds), 5905);
+
+        let rules = Rules::parse("classifier = \"cards-only\"\n").unwrap();
+        assert_eq!(
+            rules.winnings(&rounds),
+            765 + 2 * 684 + 3 * 483 + 4 * 220 + 5 * 28
+        );
+    }
+
+    #[test]
+    fn test_sort_key() {
+        let rounds = parse_input(&generate_input(500, 7)).unwrap();
+
+        for rules in [Rules::part1(), Rules::part2()] {
+            let pair = |hand: &Hand| {
+                (
+                    rules.hand_type(hand).rank(),
+                    hand.0.map(|c| rules.card_rank(c)),
+                )
+            };
+            for (a, b) in rounds.iter().zip(rounds.iter().skip(1)) {
+                assert_eq!(
+                    rules.sort_key(&a.hand).cmp(&rules.sort_key(&b.hand)),
+                    pair(&a.hand).cmp(&pair(&b.hand)),
+                    "{} {}",
+                    a.hand,
+                    b.hand
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_substitution() {
+        let rules = Rules::part2();
+
+        assert_eq!(rules.substitution(&Hand(*b"32T3K")), None);
+        assert_eq!(rules.substitution(&Hand(*b"KTJJT")), Some(Hand(*b"KTTTT")));
+        assert_eq!(rules.substitution(&Hand(*b"2J3KQ")), Some(Hand(*b"2K3KQ")));
+        assert_eq!(rules.substitution(&Hand(*b"JJJJJ")), Some(Hand(*b"AAAAA")));
+        assert_eq!(Rules::part1().substitution(&Hand(*b"KTJJT")), None);
+
+        // The substitute hand is as good as the wildcards made it
+        let rounds = parse_input(&generate_input(500, 3)).unwrap();
+        for round in rounds.iter() {
+            let substituted = rules.substitution(&round.hand).unwrap_or(round.hand);
+            assert_eq!(
+                Rules::part1().hand_type(&substituted),
+                rules.hand_type(&round.hand),
+                "{}",
+                round.hand
+            );
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let rounds =
+            parse_input(&crate::aoc::example::example_string("day7.txt").unwrap()).unwrap();
+        let explanation = Rules::part2().explain(&rounds);
+
+        assert_eq!(
+            explanation.to_string(),
+            "\
+Rank  Hand   Bid  Type             As     Winnings
+   1  32T3K  765  one pair         -           765
+   2  KK677   28  two pair         -            56
+   3  T55J5  684  four of a kind   T5555      2052
+   4  QQQJA  483  four of a kind   QQQQA      1932
+   5  KTJJT  220  four of a kind   KTTTT      1100
+Total winnings: 5905
+"
+        );
+    }
+
+    #[test]
+    fn test_rules_wildcards() {
+        let rules = Rules::parse("wildcards = \"23\" # two of them\n").unwrap();
+        assert_eq!(rules, Rules::parse("wildcards = \"23\"#\"4\"").unwrap());
+        assert_eq!(rules.hand_type(&Hand(*b"23456")), HandType::ThreeOfAKind);
+        assert_eq!(rules.hand_type(&Hand(*b"22333")), HandType::FiveOfAKind);
+        assert_eq!(rules.hand_type(&Hand(*b"2K3QQ")), HandType::FourOfAKind);
+    }
+
+    #[test]
+    fn test_rules_errors() {
+        let message = |input: &str| match Rules::parse(input).unwrap_err() {
+            Error::Parse { message, .. } => message,
+            e => panic!("{e}"),
+        };
+
+        assert!(message("order = \"AKQJT98765432\"\nwildcards = \"X\"").contains("`X`"));
+        assert!(message("order = \"AKQJT9876543\"").contains("each of"));
+        assert!(message("order = \"AKQJT98765432J\"").contains("each of"));
+        assert!(message("classifier = \"poker\"").contains("unknown classifier"));
+        assert!(message("colour = \"red\"").contains("expected `order`"));
+        assert!(message("order = AKQ").contains("quoted string"));
+        assert!(message("[rules]").contains("key = "));
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(rounds: &Rounds) -> usize {
+        Rules::part1().winnings(rounds)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_card_rank() {
+            let rules = Rules::part1();
+            assert!(rules.card_rank(b'2') < rules.card_rank(b'9'));
+            assert!(rules.card_rank(b'9') < rules.card_rank(b'J'));
+        }
+
+        #[test]
+        fn test_example() {
+            let rounds =
+                parse_input(&crate::aoc::example::example_string("day7.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&rounds), 6440);
+        }
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(rounds: &Rounds) -> usize {
+        Rules::part2().winnings(rounds)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_card_rank() {
+            let rules = Rules::part2();
+            assert!(rules.card_rank(b'2') < rules.card_rank(b'9'));
+            assert!(rules.card_rank(b'J') < rules.card_rank(b'2'));
+        }
+
+        #[test]
+        fn test_example() {
+            let rounds =
+                parse_input(&crate::aoc::example::example_string("day7.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&rounds), 5905);
+        }
+    }
+}
+
+/// A puzzle input of `rounds` random rounds, the same for the same `seed`,
+/// for timing on inputs much larger than the real one.
+pub fn generate_input(rounds: usize, seed: u64) -> String {
+    // xorshift64, which only needs a state that isn't zero
+    let mut state = seed | 1;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut input = String::with_capacity(rounds * 10);
+    for _ in 0..rounds {
+        for _ in 0..5 {
+            input.push(CARDS[(next() % CARDS.len() as u64) as usize] as char);
+        }
+        input += &format!(" {}\n", next() % 1000 + 1);
+    }
+
+    input
+}
+
+fn parse_input(s: &str) -> Result<Rounds> {
+    s.parse()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Rounds;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1::calculate(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2::calculate(input))
+    }
+}
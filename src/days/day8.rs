@@ -0,0 +1,653 @@
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+    str::{from_utf8, FromStr},
+};
+
+use regex::Regex;
+
+use crate::{
+    aoc::{Error, Result, Solution},
+    graph::{find_loop, Interner, NodeId},
+    quant::crt,
+};
+
+type Node = [u8; 3];
+
+/// A way of picking out nodes by name.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Exact(Node),
+    Suffix(String),
+    Regex(Regex),
+}
+
+impl Pattern {
+    pub fn matches(&self, name: &Node) -> bool {
+        match self {
+            Pattern::Exact(node) => node == name,
+            Pattern::Suffix(suffix) => name.ends_with(suffix.as_bytes()),
+            Pattern::Regex(regex) => regex.is_match(&String::from_utf8_lossy(name)),
+        }
+    }
+}
+
+/// `AAA` for that node, `*Z` for every node ending in `Z`, or `/^.[XY]Z$/`
+/// for every node matching a regex.
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        if let Some(regex) = s.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            return Regex::new(regex)
+                .map(Pattern::Regex)
+                .map_err(|e| e.to_string());
+        }
+        if let Some(suffix) = s.strip_prefix('*') {
+            return Ok(Pattern::Suffix(suffix.to_owned()));
+        }
+
+        s.as_bytes().try_into().map(Pattern::Exact).map_err(|_| {
+            format!("expected a 3 letter node name, `*<suffix>` or `/<regex>/`, got `{s}`")
+        })
+    }
+}
+
+/// The nodes matching any of a list of patterns.
+#[derive(Debug, Clone)]
+pub struct NodeSet(pub Vec<Pattern>);
+
+impl NodeSet {
+    pub fn exact(node: Node) -> Self {
+        Self(vec![Pattern::Exact(node)])
+    }
+
+    pub fn suffix(suffix: &str) -> Self {
+        Self(vec![Pattern::Suffix(suffix.to_owned())])
+    }
+
+    /// Where part 2's ghosts start.
+    pub fn ghost_starts() -> Self {
+        Self::suffix("A")
+    }
+
+    /// Where part 2's ghosts are heading.
+    pub fn ghost_ends() -> Self {
+        Self::suffix("Z")
+    }
+
+    pub fn matches(&self, name: &Node) -> bool {
+        self.0.iter().any(|pattern| pattern.matches(name))
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    steps: Vec<Step>,
+    names: Interner<Node>,
+    /// The left and right node from each node.
+    network: Vec<(NodeId, NodeId)>,
+}
+
+impl Input {
+    pub fn iter_steps(&self) -> StepsIter<'_> {
+        StepsIter::new(&self.steps)
+    }
+
+    /// How many distinct (node, step) states a walk can be in, so any walk
+    /// longer than this is going round in circles.
+    pub fn state_count(&self) -> usize {
+        self.network.len() * self.steps.len()
+    }
+
+    pub fn id(&self, name: &Node) -> Option<NodeId> {
+        self.names.get(name)
+    }
+
+    pub fn name(&self, node: NodeId) -> &Node {
+        &self.names[node]
+    }
+
+    pub fn next(&self, node: NodeId, step: Step) -> NodeId {
+        let (left, right) = self.network[node as usize];
+        match step {
+            Step::L => left,
+            Step::R => right,
+        }
+    }
+
+    /// Every node in `set`, in the order they're listed.
+    pub fn nodes<'a>(&'a self, set: &'a NodeSet) -> impl Iterator<Item = NodeId> + 'a {
+        self.names
+            .iter()
+            .filter(|(_, name)| set.matches(name))
+            .map(|(node, _)| node)
+    }
+
+    /// Every node visited following the steps from `start` to the first node
+    /// in `goal`, both included.
+    pub fn walk(&self, start: NodeId, goal: &NodeSet) -> Result<Vec<NodeId>> {
+        let mut node = start;
+        let mut path = vec![node];
+        for step in self.iter_steps().take(self.state_count() + 1) {
+            if goal.matches(self.name(node)) {
+                return Ok(path);
+            }
+            node = self.next(node, step);
+            path.push(node);
+        }
+
+        Err(Error::Unsolvable(format!(
+            "no goal node can be reached from {}",
+            String::from_utf8_lossy(self.name(start))
+        )))
+    }
+
+    /// Every node the part 1 walk visits, from AAA to ZZZ inclusive.
+    pub fn part1_path(&self) -> Result<Vec<NodeId>> {
+        let start = self
+            .id(b"AAA")
+            .ok_or_else(|| Error::Unsolvable("there is no node AAA".to_owned()))?;
+
+        self.walk(start, &NodeSet::exact(*b"ZZZ"))
+    }
+
+    /// The network as a Graphviz digraph, with nodes in `starts` and `goals`
+    /// filled in and the edges along each of `paths`, walks following the
+    /// steps from their first node, drawn in red.
+    pub fn to_dot(&self, starts: &NodeSet, goals: &NodeSet, paths: &[Vec<NodeId>]) -> String {
+        // Every node but the last is left by the next step
+        let walked: HashSet<(NodeId, Step)> = paths
+            .iter()
+            .flat_map(|path| {
+                path[..path.len().saturating_sub(1)]
+                    .iter()
+                    .zip(self.iter_steps())
+                    .map(|(&node, step)| (node, step))
+            })
+            .collect();
+        let name = |node: NodeId| String::from_utf8_lossy(self.name(node)).into_owned();
+
+        let mut dot = "digraph network {\n    node [shape=circle];\n".to_owned();
+        for (_, name) in self.names.iter() {
+            let fill = match (starts.matches(name), goals.matches(name)) {
+                (true, _) => "palegreen",
+                (_, true) => "lightcoral",
+                _ => continue,
+            };
+            let name = String::from_utf8_lossy(name);
+            writeln!(dot, "    \"{name}\" [style=filled, fillcolor={fill}];").unwrap();
+        }
+
+        for (node, &(left, right)) in self.network.iter().enumerate() {
+            let node = node as NodeId;
+            for (step, target) in [(Step::L, left), (Step::R, right)] {
+                let style = match walked.contains(&(node, step)) {
+                    true => ", color=red, penwidth=2",
+                    false => "",
+                };
+                writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\" [label={step}{style}];",
+                    name(node),
+                    name(target)
+                )
+                .unwrap();
+            }
+        }
+
+        dot + "}\n"
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            write!(f, "{:?}", step)?;
+        }
+        f.write_str("\n\n")?;
+
+        for (node, &(l, r)) in self.network.iter().enumerate() {
+            writeln!(
+                f,
+                "{} => ({}, {})",
+                from_utf8(self.name(node as NodeId)).unwrap(),
+                from_utf8(self.name(l)).unwrap(),
+                from_utf8(self.name(r)).unwrap()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    L,
+    R,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char(match self {
+            Step::L => 'L',
+            Step::R => 'R',
+        })
+    }
+}
+
+pub struct StepsIter<'a> {
+    steps: &'a Vec<Step>,
+    idx: usize,
+}
+
+impl<'a> StepsIter<'a> {
+    pub fn new(steps: &'a Vec<Step>) -> Self {
+        Self { steps, idx: 0 }
+    }
+}
+
+impl<'a> Iterator for StepsIter<'a> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = self.steps[self.idx];
+        self.idx = (self.idx + 1) % self.steps.len();
+        Some(step)
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(input: &Input) -> Result<usize> {
+        Ok(input.part1_path()?.len() - 1)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(
+                calculate(
+                    &parse_input(&crate::aoc::example::example_string("day8_1.txt").unwrap())
+                        .unwrap()
+                )
+                .unwrap(),
+                2
+            );
+            assert_eq!(
+                calculate(
+                    &parse_input(&crate::aoc::example::example_string("day8_2.txt").unwrap())
+                        .unwrap()
+                )
+                .unwrap(),
+                6
+            );
+        }
+
+        #[test]
+        fn test_dot() {
+            let input =
+                parse_input(&crate::aoc::example::example_string("day8_2.txt").unwrap()).unwrap();
+            let path = input.part1_path().unwrap();
+            let names: Vec<_> = path.iter().map(|&node| input.name(node)).collect();
+            assert_eq!(
+                names,
+                [b"AAA", b"BBB", b"AAA", b"BBB", b"AAA", b"BBB", b"ZZZ"]
+            );
+
+            let dot = input.to_dot(&NodeSet::ghost_starts(), &NodeSet::ghost_ends(), &[path]);
+            assert!(dot.starts_with("digraph network {\n"));
+            assert!(dot.contains("    \"AAA\" [style=filled, fillcolor=palegreen];\n"));
+            assert!(dot.contains("    \"ZZZ\" [style=filled, fillcolor=lightcoral];\n"));
+            // LLR: AAA -L-> BBB -L-> AAA -R-> BBB -L-> AAA -L-> BBB -R-> ZZZ
+            assert!(dot.contains("    \"AAA\" -> \"BBB\" [label=L, color=red, penwidth=2];\n"));
+            assert!(dot.contains("    \"BBB\" -> \"ZZZ\" [label=R, color=red, penwidth=2];\n"));
+            assert!(dot.contains("    \"ZZZ\" -> \"ZZZ\" [label=L];\n"));
+            assert!(dot.ends_with("}\n"));
+        }
+
+        #[test]
+        fn test_patterns() {
+            let names = |set: &[&str]| {
+                let input = parse_input(
+                    "L\n\nAAA = (AAZ, AAZ)\nAAZ = (XYZ, XYZ)\nXYZ = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+                )
+                .unwrap();
+                let set = NodeSet(set.iter().map(|p| p.parse().unwrap()).collect());
+                input
+                    .nodes(&set)
+                    .map(|node| String::from_utf8_lossy(input.name(node)).into_owned())
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(names(&["XYZ"]), ["XYZ"]);
+            assert_eq!(names(&["*Z"]), ["AAZ", "XYZ", "ZZZ"]);
+            assert_eq!(names(&["*ZZ"]), ["ZZZ"]);
+            assert_eq!(names(&["/^A/", "ZZZ"]), ["AAA", "AAZ", "ZZZ"]);
+            assert_eq!(names(&["/[XY]/"]), ["XYZ"]);
+
+            assert!("AAAA".parse::<Pattern>().is_err());
+            assert!("/[/".parse::<Pattern>().is_err());
+        }
+
+        #[test]
+        fn test_walk() {
+            let input =
+                parse_input(&crate::aoc::example::example_string("day8_3.txt").unwrap()).unwrap();
+            let start = input.id(b"22A").unwrap();
+            let path = input.walk(start, &NodeSet::ghost_ends()).unwrap();
+            let names: Vec<_> = path.iter().map(|&node| input.name(node)).collect();
+            assert_eq!(names, [b"22A", b"22B", b"22C", b"22Z"]);
+
+            // Already there
+            let goal = "/2.A/".parse().unwrap();
+            assert_eq!(input.walk(start, &NodeSet(vec![goal])).unwrap(), [start]);
+
+            // 11A never reaches anything starting 22
+            let start = input.id(b"11A").unwrap();
+            assert!(input.walk(start, &NodeSet::exact(*b"22Z")).is_err());
+        }
+
+        #[test]
+        fn test_parse_errors() {
+            let line = |input: &str| match parse_input(input).unwrap_err() {
+                Error::Parse { line, message, .. } => (line, message),
+                e => panic!("{e}"),
+            };
+
+            assert_eq!(
+                line("L\n\nAAA = (BBB, AAA)\n"),
+                (3, "unknown node `BBB`".to_owned())
+            );
+            assert_eq!(
+                line("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)\n"),
+                (4, "node listed twice".to_owned())
+            );
+        }
+    }
+}
+
+/// Where a walk from one start node goes: after `prefix` steps it is in a
+/// loop of `cycle` steps that it goes round forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost {
+    pub start: Node,
+    pub prefix: usize,
+    pub cycle: usize,
+    /// Steps before the loop at which the walk is on an end node.
+    pub prefix_ends: Vec<usize>,
+    /// Steps in the first time round the loop, from `prefix` up to
+    /// `prefix + cycle`, at which the walk is on an end node.
+    pub cycle_ends: Vec<usize>,
+}
+
+impl Ghost {
+    pub fn is_end_at(&self, steps: usize) -> bool {
+        if steps < self.prefix {
+            self.prefix_ends.contains(&steps)
+        } else {
+            let offset = self.prefix + (steps - self.prefix) % self.cycle;
+            self.cycle_ends.contains(&offset)
+        }
+    }
+}
+
+impl Input {
+    /// Follow the walk from `start` through its (node, step index) states
+    /// until it has been once round its loop, noting when it's in `goal`.
+    pub fn ghost(&self, start: NodeId, goal: &NodeSet) -> Ghost {
+        let next = |&(node, i): &(NodeId, usize)| {
+            (self.next(node, self.steps[i]), (i + 1) % self.steps.len())
+        };
+        let (prefix, cycle) = find_loop((start, 0), next);
+
+        let mut state = (start, 0);
+        let mut ends = vec![];
+        for i in 0..prefix + cycle {
+            if goal.matches(self.name(state.0)) {
+                ends.push(i);
+            }
+            state = next(&state);
+        }
+        let (prefix_ends, cycle_ends) = ends.into_iter().partition(|&end| end < prefix);
+
+        Ghost {
+            start: *self.name(start),
+            prefix,
+            cycle,
+            prefix_ends,
+            cycle_ends,
+        }
+    }
+}
+
+/// The first step at which every ghost is on an end node at once.
+pub fn first_common_end(ghosts: &[Ghost]) -> Result<usize> {
+    /// How many ways of picking one end from each loop to try.
+    const MAX_COMBINATIONS: usize = 1 << 16;
+
+    if ghosts.is_empty() {
+        return Err(Error::Unsolvable("there are no start nodes".to_owned()));
+    }
+    if let Some(ghost) = ghosts
+        .iter()
+        .find(|g| g.prefix_ends.is_empty() && g.cycle_ends.is_empty())
+    {
+        return Err(Error::Unsolvable(format!(
+            "no end node can be reached from {}",
+            String::from_utf8_lossy(&ghost.start)
+        )));
+    }
+
+    // Before every ghost is in its loop, just look
+    let prefix = ghosts.iter().map(|g| g.prefix).max().unwrap();
+    if let Some(steps) = (0..prefix).find(|&steps| ghosts.iter().all(|g| g.is_end_at(steps))) {
+        return Ok(steps);
+    }
+
+    // After, each ghost is on an end node at steps congruent to one of its
+    // loop's ends, so try every combination of those
+    let combinations = ghosts
+        .iter()
+        .try_fold(1usize, |n, g| n.checked_mul(g.cycle_ends.len()))
+        .filter(|&n| n <= MAX_COMBINATIONS)
+        .ok_or_else(|| {
+            Error::Unsolvable("too many ways for the ghosts' loops to line up".to_owned())
+        })?;
+
+    let mut best: Option<u128> = None;
+    for mut i in 0..combinations {
+        let congruences = ghosts.iter().map(|ghost| {
+            let end = ghost.cycle_ends[i % ghost.cycle_ends.len()];
+            i /= ghost.cycle_ends.len();
+            (end as u128, ghost.cycle as u128)
+        });
+
+        // The smallest solution no earlier than when every ghost is looping
+        if let Some((x, m)) = crt(congruences) {
+            let behind = (prefix as u128).saturating_sub(x);
+            let steps = x + behind.div_ceil(m) * m;
+            best = Some(best.map_or(steps, |best| best.min(steps)));
+        }
+    }
+
+    let steps = best.ok_or_else(|| {
+        Error::Unsolvable("the ghosts are never all on end nodes at once".to_owned())
+    })?;
+    usize::try_from(steps)
+        .map_err(|_| Error::Unsolvable(format!("{steps} steps is too many to count")))
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(input: &Input) -> Result<usize> {
+        let ends = NodeSet::ghost_ends();
+        let ghosts: Vec<_> = input
+            .nodes(&NodeSet::ghost_starts())
+            .map(|start| input.ghost(start, &ends))
+            .collect();
+
+        first_common_end(&ghosts)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(
+                calculate(
+                    &parse_input(&crate::aoc::example::example_string("day8_3.txt").unwrap())
+                        .unwrap()
+                )
+                .unwrap(),
+                6
+            );
+        }
+
+        #[test]
+        fn test_not_lcm() {
+            // 11A reaches 11Z after 2, 4, 6, ... steps and 22A after 1, 4,
+            // 7, ... steps, so the lcm of the first hits would say 2
+            let input = parse_input(
+                "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+                 22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n",
+            )
+            .unwrap();
+
+            let ghost = input.ghost(input.id(b"22A").unwrap(), &NodeSet::ghost_ends());
+            assert_eq!((ghost.prefix, ghost.cycle), (1, 3));
+            assert_eq!((ghost.prefix_ends, ghost.cycle_ends), (vec![], vec![1]));
+            assert_eq!(calculate(&input).unwrap(), 4);
+        }
+
+        #[test]
+        fn test_unsolvable() {
+            // 33A is only on 33Z once, before the others start looping
+            let input = parse_input(
+                "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+                 33A = (33Z, 33Z)\n33Z = (33B, 33B)\n33B = (33B, 33B)\n",
+            )
+            .unwrap();
+            assert_eq!(
+                input
+                    .ghost(input.id(b"33A").unwrap(), &NodeSet::ghost_ends())
+                    .prefix_ends,
+                [1]
+            );
+            assert!(matches!(calculate(&input), Err(Error::Unsolvable(_))));
+
+            let input = parse_input("L\n\n11A = (11B, 11B)\n11B = (11A, 11A)\n").unwrap();
+            assert!(matches!(calculate(&input), Err(Error::Unsolvable(_))));
+        }
+    }
+}
+
+fn parse_name(line: &str, name: &str) -> Result<Node> {
+    name.as_bytes()
+        .try_into()
+        .map_err(|_| Error::parse(line, name, "expected a 3 letter node name"))
+}
+
+fn parse_node(line: &str) -> Result<(Node, (Node, Node))> {
+    const EXPECTED: &str = "expected `<node> = (<left>, <right>)`";
+
+    let (name, targets) = line
+        .split_once(" = ")
+        .ok_or_else(|| Error::parse(line, line, EXPECTED))?;
+    let (left, right) = targets
+        .strip_prefix('(')
+        .and_then(|targets| targets.strip_suffix(')'))
+        .and_then(|targets| targets.split_once(", "))
+        .ok_or_else(|| Error::parse(line, targets, EXPECTED))?;
+
+    Ok((
+        parse_name(line, name)?,
+        (parse_name(line, left)?, parse_name(line, right)?),
+    ))
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let mut lines = input.lines();
+
+    let steps_line = lines.next().unwrap_or_default();
+    let steps: Vec<Step> = steps_line
+        .char_indices()
+        .map(|(i, step)| match step {
+            'L' => Ok(Step::L),
+            'R' => Ok(Step::R),
+            _ => Err(Error::parse(input, &steps_line[i..], "expected `L` or `R`")),
+        })
+        .collect::<Result<_>>()?;
+    if steps.is_empty() {
+        return Err(Error::parse(input, steps_line, "expected a list of steps"));
+    }
+
+    if let Some(line) = lines.next().filter(|line| !line.is_empty()) {
+        return Err(Error::parse(input, line, "expected a blank line"));
+    }
+
+    let nodes = lines
+        .enumerate()
+        .map(|(i, line)| Ok((line, parse_node(line).map_err(|e| e.offset_lines(i + 2))?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Number the nodes in the order they're listed, before any are used
+    let mut names = Interner::new();
+    for (line, (name, _)) in &nodes {
+        if names.get(name).is_some() {
+            return Err(Error::parse(input, line, "node listed twice"));
+        }
+        names.intern(*name);
+    }
+
+    let network = nodes
+        .iter()
+        .map(|(line, (_, targets))| {
+            let id = |target: &Node| {
+                names.get(target).ok_or_else(|| {
+                    Error::parse(
+                        input,
+                        line,
+                        format!("unknown node `{}`", String::from_utf8_lossy(target)),
+                    )
+                })
+            };
+            Ok((id(&targets.0)?, id(&targets.1)?))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Input {
+        steps,
+        names,
+        network,
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        part1::calculate(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2::calculate(input)
+    }
+}
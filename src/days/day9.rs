@@ -0,0 +1,249 @@
+use std::str::FromStr;
+
+use crate::{
+    aoc::{
+        parse::{parse_lines, parse_num},
+        Error, Result, Solution,
+    },
+    poly::{Newton, Shape},
+};
+
+pub type Sequence = Vec<isize>;
+
+fn parse_input(input: &str) -> Result<Vec<Sequence>> {
+    parse_lines(input, |line| {
+        line.split_ascii_whitespace()
+            .map(|n| parse_num(line, n))
+            .collect()
+    })
+}
+
+/// The polynomial through `seq`, with its first value at position 0.
+pub fn fit(seq: &Sequence) -> Result<Newton> {
+    let samples: Vec<_> = seq.iter().map(|&n| n as i128).collect();
+    Newton::fit(&samples)
+        .ok_or_else(|| Error::Unsolvable("differences don't fit in an i128".to_owned()))
+}
+
+/// The value at `position` in the sequence `newton` was fitted to, which may
+/// be far outside it.
+pub fn value_at(newton: &Newton, position: i128) -> Result<i128> {
+    newton
+        .at(position)
+        .ok_or_else(|| Error::Unsolvable(format!("the value at {position} doesn't fit in an i128")))
+}
+
+/// The value `steps` places after the last one.
+pub fn extrapolate(seq: &Sequence, steps: i128) -> Result<i128> {
+    let position = (seq.len() as i128 - 1)
+        .checked_add(steps)
+        .ok_or_else(|| Error::Unsolvable(format!("{steps} steps is too far")))?;
+    value_at(&fit(seq)?, position)
+}
+
+/// The value `steps` places before the first one.
+pub fn extrapolate_backwards(seq: &Sequence, steps: i128) -> Result<i128> {
+    value_at(&fit(seq)?, -steps)
+}
+
+/// What to do with sequences that aren't clearly polynomial, whose
+/// extrapolations can't be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Suspicious {
+    #[default]
+    Fail,
+    Skip,
+}
+
+impl FromStr for Suspicious {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "fail" => Ok(Suspicious::Fail),
+            "skip" => Ok(Suspicious::Skip),
+            _ => Err(format!("unknown policy `{s}`, expected `fail` or `skip`")),
+        }
+    }
+}
+
+/// Every sequence's shape, by line number counting from 1.
+pub fn classify(sequences: &[Sequence]) -> Result<Vec<(usize, Shape)>> {
+    fit_lines(sequences)
+        .map(|(line, newton)| Ok((line, newton?.shape())))
+        .collect()
+}
+
+fn fit_lines(sequences: &[Sequence]) -> impl Iterator<Item = (usize, Result<Newton>)> + '_ {
+    sequences.iter().enumerate().map(|(i, seq)| {
+        let newton = fit(seq).map_err(|e| match e {
+            Error::Unsolvable(reason) => Error::Unsolvable(format!("line {}: {reason}", i + 1)),
+            e => e,
+        });
+        (i + 1, newton)
+    })
+}
+
+/// Fits every sequence, failing on or leaving out those that aren't
+/// clearly polynomial as `policy` says. A failure names every such line,
+/// and the fits that are kept come with theirs.
+pub fn fit_all(sequences: &[Sequence], policy: Suspicious) -> Result<Vec<(usize, Newton)>> {
+    let mut fits = vec![];
+    let mut suspicious = vec![];
+
+    for (line, newton) in fit_lines(sequences) {
+        let newton = newton?;
+        match newton.shape() {
+            Shape::Polynomial(_) => fits.push((line, newton)),
+            shape => suspicious.push(format!("line {line} is {shape}")),
+        }
+    }
+
+    match policy {
+        Suspicious::Fail if !suspicious.is_empty() => Err(Error::Unsolvable(suspicious.join(", "))),
+        _ => Ok(fits),
+    }
+}
+
+/// Adds up the values, failing on the first error or if the total overflows.
+pub fn checked_sum(values: impl IntoIterator<Item = Result<i128>>) -> Result<i128> {
+    values.into_iter().try_fold(0i128, |total, value| {
+        total
+            .checked_add(value?)
+            .ok_or_else(|| Error::Unsolvable("the total doesn't fit in an i128".to_owned()))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extrapolate() {
+        let seq = vec![10, 13, 16, 21, 30, 45];
+        assert_eq!(extrapolate(&seq, 1).unwrap(), 68);
+        assert_eq!(extrapolate_backwards(&seq, 1).unwrap(), 5);
+        assert_eq!(extrapolate(&seq, 0).unwrap(), 45);
+        assert_eq!(extrapolate_backwards(&seq, 0).unwrap(), 10);
+
+        let newton = fit(&seq).unwrap();
+        assert_eq!(newton.degree(), Some(3));
+        assert_eq!(newton.coefficients(), &[10, 3, 0, 2]);
+
+        // 10 + 3x + 2 * binomial(x, 3)
+        let x = 1_000_000_000i128;
+        let expected = 10 + 3 * x + x * (x - 1) * (x - 2) / 3;
+        assert_eq!(extrapolate(&seq, x - 5).unwrap(), expected);
+        assert_eq!(extrapolate(&seq, i128::MAX).ok(), None);
+        assert_eq!(extrapolate(&seq, 1 << 60).ok(), None);
+    }
+
+    #[test]
+    fn test_suspicious() {
+        let sequences = parse_input("0 3 6 9 12 15\n1 2 4 8 16\n5\n1 3 6 10 15 21\n").unwrap();
+        let shapes: Vec<_> = classify(&sequences)
+            .unwrap()
+            .into_iter()
+            .map(|(line, shape)| (line, shape.to_string()))
+            .collect();
+        assert_eq!(
+            shapes,
+            [
+                (1, "polynomial of degree 1".to_owned()),
+                (2, "not polynomial".to_owned()),
+                (3, "too short to tell".to_owned()),
+                (4, "polynomial of degree 2".to_owned()),
+            ]
+        );
+
+        match fit_all(&sequences, Suspicious::Fail) {
+            Err(Error::Unsolvable(reason)) => assert_eq!(
+                reason,
+                "line 2 is not polynomial, line 3 is too short to tell"
+            ),
+            other => panic!("expected an error, got {other:?}"),
+        }
+        assert_eq!(
+            part1::calculate(&sequences, Suspicious::Skip).unwrap(),
+            18 + 28
+        );
+        assert_eq!(part2::calculate(&sequences, Suspicious::Skip).unwrap(), -3);
+
+        // Alternating extremes double their differences on every row
+        let extremes = (0..70).map(|i| [isize::MAX, isize::MIN][i % 2]).collect();
+        let sequences = vec![vec![1, 1], extremes];
+        match fit_all(&sequences, Suspicious::Skip) {
+            Err(Error::Unsolvable(reason)) => assert!(reason.starts_with("line 2: "), "{reason}"),
+            other => panic!("expected an error, got {other:?}"),
+        }
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn calculate(sequences: &[Sequence], policy: Suspicious) -> Result<i128> {
+        let fits = fit_all(sequences, policy)?;
+        checked_sum(
+            fits.iter()
+                .map(|(_, newton)| value_at(newton, newton.samples() as i128)),
+        )
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let sequences =
+                parse_input(&crate::aoc::example::example_string("day9.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&sequences, Suspicious::Fail).unwrap(), 114)
+        }
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn calculate(sequences: &[Sequence], policy: Suspicious) -> Result<i128> {
+        let fits = fit_all(sequences, policy)?;
+        checked_sum(fits.iter().map(|(_, newton)| value_at(newton, -1)))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            let sequences =
+                parse_input(&crate::aoc::example::example_string("day9.txt").unwrap()).unwrap();
+
+            assert_eq!(calculate(&sequences, Suspicious::Fail).unwrap(), 2)
+        }
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Sequence>;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        part1::calculate(input, Suspicious::Fail)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2::calculate(input, Suspicious::Fail)
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::{Add, Index},
+};
+
+/// A dense index for a node, handed out in order from zero.
+pub type NodeId = u32;
+
+/// Gives each distinct name a [`NodeId`], remembering the name for display.
+#[derive(Debug, Clone)]
+pub struct Interner<T> {
+    ids: HashMap<T, NodeId>,
+    names: Vec<T>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            names: vec![],
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id for `name`, giving it the next one if it hasn't got one yet.
+    pub fn intern(&mut self, name: T) -> NodeId {
+        if let Some(&id) = self.ids.get(&name) {
+            return id;
+        }
+
+        let id = self.names.len() as NodeId;
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        id
+    }
+
+    pub fn get(&self, name: &T) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &T {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Every name with its id, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id as NodeId, name))
+    }
+}
+
+impl<T: Hash + Eq + Clone> Index<NodeId> for Interner<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        self.name(id)
+    }
+}
+
+/// A directed graph stored as an adjacency list, with a value of type `E` on
+/// every edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<E = ()> {
+    edges: Vec<Vec<(NodeId, E)>>,
+}
+
+impl<E> Default for Graph<E> {
+    fn default() -> Self {
+        Self { edges: vec![] }
+    }
+}
+
+impl<E> Graph<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A graph of `nodes` nodes with no edges.
+    pub fn with_nodes(nodes: usize) -> Self {
+        Self {
+            edges: (0..nodes).map(|_| vec![]).collect(),
+        }
+    }
+
+    pub fn add_node(&mut self) -> NodeId {
+        self.edges.push(vec![]);
+        (self.edges.len() - 1) as NodeId
+    }
+
+    /// Add an edge, adding nodes up to the larger end if they don't exist.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, edge: E) {
+        let needed = from.max(to) as usize + 1;
+        if self.edges.len() < needed {
+            self.edges.resize_with(needed, Vec::new);
+        }
+        self.edges[from as usize].push((to, edge));
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.edges.len() as NodeId
+    }
+
+    pub fn edges(&self, from: NodeId) -> &[(NodeId, E)] {
+        &self.edges[from as usize]
+    }
+
+    pub fn neighbours(&self, from: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges(from).iter().map(|&(to, _)| to)
+    }
+
+    /// The number of edges on the shortest walk from `start` to each node, if
+    /// there is one.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        distances[start as usize] = Some(0);
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node as usize].unwrap();
+            for next in self.neighbours(node) {
+                if distances[next as usize].is_none() {
+                    distances[next as usize] = Some(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// A shortest walk from `start` to the nearest node matching `goal`,
+    /// including both ends.
+    pub fn bfs_path(&self, start: NodeId, goal: impl Fn(NodeId) -> bool) -> Option<Vec<NodeId>> {
+        let mut parents: Vec<Option<NodeId>> = vec![None; self.len()];
+        let mut seen = vec![false; self.len()];
+        seen[start as usize] = true;
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            if goal(node) {
+                let mut path = vec![node];
+                while let Some(parent) = parents[*path.last().unwrap() as usize] {
+                    path.push(parent);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for next in self.neighbours(node) {
+                if !seen[next as usize] {
+                    seen[next as usize] = true;
+                    parents[next as usize] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Every node reachable from `start`, in depth first preorder.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if std::mem::replace(&mut seen[node as usize], true) {
+                continue;
+            }
+            order.push(node);
+
+            // Reversed so the first edge is explored first
+            stack.extend(
+                self.neighbours(node)
+                    .filter(|&next| !seen[next as usize])
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev(),
+            );
+        }
+
+        order
+    }
+
+    /// Some cycle of the graph as the nodes around it, starting from the
+    /// one it was entered at, if it has any.
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        for root in self.nodes() {
+            if state[root as usize] != State::New {
+                continue;
+            }
+
+            // The current path, with how many of each node's edges are done
+            let mut path: Vec<(NodeId, usize)> = vec![(root, 0)];
+            state[root as usize] = State::OnPath;
+
+            while let Some((node, i)) = path.last_mut() {
+                let node = *node;
+                match self.edges(node).get(*i) {
+                    Some(&(next, _)) => {
+                        *i += 1;
+                        match state[next as usize] {
+                            State::New => {
+                                state[next as usize] = State::OnPath;
+                                path.push((next, 0));
+                            }
+                            State::OnPath => {
+                                let from = path.iter().position(|&(n, _)| n == next).unwrap();
+                                return Some(path[from..].iter().map(|&(n, _)| n).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[node as usize] = State::Done;
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<E: Copy + Ord + Default + Add<Output = E>> Graph<E> {
+    /// The total weight of the lightest walk from `start` to each node, if
+    /// there is one, treating each edge's value as its weight.
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<E>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((E::default(), start))]);
+
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if distances[node as usize].is_some() {
+                continue;
+            }
+            distances[node as usize] = Some(distance);
+
+            for &(next, weight) in self.edges(node) {
+                if distances[next as usize].is_none() {
+                    queue.push(Reverse((distance + weight, next)));
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+/// Where repeatedly applying `f` from `start` starts going round in circles,
+/// as how many steps it takes to get into the loop and how long the loop is.
+/// Uses Brent's algorithm, so only ever holds a couple of states.
+pub fn find_loop<S: Clone + Eq>(start: S, f: impl Fn(&S) -> S) -> (usize, usize) {
+    // Find the loop's length by racing ahead in powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // Then walk two states that far apart along until they meet
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    (prefix, length)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 and 0 -> 4 -> 3, with 5 off on its own.
+    fn diamond() -> Graph<u32> {
+        let mut graph = Graph::with_nodes(6);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(0, 4, 5);
+        graph.add_edge(4, 3, 1);
+        graph
+    }
+
+    #[test]
+    fn test_interner() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("AAA"), 0);
+        assert_eq!(names.intern("BBB"), 1);
+        assert_eq!(names.intern("AAA"), 0);
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.get(&"BBB"), Some(1));
+        assert_eq!(names.get(&"CCC"), None);
+        assert_eq!(names[1], "BBB");
+        assert_eq!(names.iter().collect::<Vec<_>>(), [(0, &"AAA"), (1, &"BBB")]);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let graph = diamond();
+        assert_eq!(
+            graph.bfs(0),
+            [Some(0), Some(1), Some(2), Some(2), Some(1), None]
+        );
+        assert_eq!(graph.bfs_path(0, |n| n == 3), Some(vec![0, 4, 3]));
+        assert_eq!(graph.bfs_path(0, |n| n == 0), Some(vec![0]));
+        assert_eq!(graph.bfs_path(0, |n| n == 5), None);
+    }
+
+    #[test]
+    fn test_dfs() {
+        assert_eq!(diamond().dfs(0), [0, 1, 2, 3, 4]);
+        assert_eq!(diamond().dfs(5), [5]);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            diamond().dijkstra(0),
+            [Some(0), Some(1), Some(2), Some(3), Some(5), None]
+        );
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph = diamond();
+        assert_eq!(graph.find_cycle(), None);
+
+        graph.add_edge(3, 1, 1);
+        assert_eq!(graph.find_cycle(), Some(vec![1, 2, 3]));
+
+        let mut graph = Graph::new();
+        graph.add_edge(0, 0, ());
+        assert_eq!(graph.find_cycle(), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_find_loop() {
+        // 0, 1, 2, then 3..10 over and over
+        let next = |&n: &u32| if n == 9 { 3 } else { n + 1 };
+        assert_eq!(find_loop(0, next), (3, 7));
+        assert_eq!(find_loop(5, next), (0, 7));
+        assert_eq!(find_loop(0, |_| 0), (0, 1));
+    }
+}
@@ -0,0 +1,466 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Index, IndexMut, Sub},
+    str::FromStr,
+};
+
+use crate::aoc::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The 4 points sharing an edge with this one, clockwise from north.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINAL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The 8 points sharing an edge or corner with this one, clockwise from
+    /// north.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A compass direction, with y increasing southwards as it does down the
+/// lines of a puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction::N => Point::new(0, -1),
+            Direction::NE => Point::new(1, -1),
+            Direction::E => Point::new(1, 0),
+            Direction::SE => Point::new(1, 1),
+            Direction::S => Point::new(0, 1),
+            Direction::SW => Point::new(-1, 1),
+            Direction::W => Point::new(-1, 0),
+            Direction::NW => Point::new(-1, -1),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub const fn turn_cw(self) -> Self {
+        self.turn(2)
+    }
+
+    pub const fn turn_ccw(self) -> Self {
+        self.turn(6)
+    }
+
+    /// Turn clockwise by `eighths` of a full turn.
+    const fn turn(self, eighths: usize) -> Self {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+/// A dense, rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid from its cells in row order. Panics if there aren't
+    /// `width * height` of them.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid is not {width}x{height}");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x as isize, y as isize)))
+            .map(&mut f)
+            .collect();
+
+        Self::new(width, height, cells)
+    }
+
+    /// Parse one cell per character, with a row per line. Every row has to be
+    /// the same length and `f` decides which characters are valid cells.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            let mut row_width = 0;
+            for (i, c) in line.char_indices() {
+                cells.push(
+                    f(c).ok_or_else(|| {
+                        Error::parse(input, &line[i..], format!("unexpected `{c}`"))
+                    })?,
+                );
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(Error::parse(
+                        input,
+                        line,
+                        format!("expected a row {width} wide, found {row_width}"),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as isize).contains(&point.x) && (0..self.height as isize).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point in the grid, in row order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| Point::new(x as isize, y as isize)))
+    }
+
+    /// Every cell with its point, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The cells sharing an edge with `point` that are inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point.neighbours4().filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// The cells sharing an edge or corner with `point` that are inside the
+    /// grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        point.neighbours8().filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// The cells in row `y`. Panics if there's no such row.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(
+            y < self.height,
+            "row {y} is outside a grid {} high",
+            self.height
+        );
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row from the top, including the empty rows of a grid with no
+    /// columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells in column `x`, from the top. Panics if there's no such
+    /// column.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} wide",
+            self.width
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells from `start` onwards in `direction`, until the edge.
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start), move |p| Some(p.step(direction)))
+            .map_while(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Every diagonal running down and to the right, starting from the
+    /// bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        let left = (0..self.height as isize).rev().map(|y| Point::new(0, y));
+        let top = (1..self.width as isize).map(|x| Point::new(x, 0));
+
+        left.chain(top).map(|start| self.ray(start, Direction::SE))
+    }
+
+    /// Every diagonal running down and to the left, starting from the top
+    /// left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        let right = self.width as isize - 1;
+        let top = (0..self.width as isize).map(|x| Point::new(x, 0));
+        let side = (1..self.height as isize).map(move |y| Point::new(right, y));
+
+        top.chain(side).map(|start| self.ray(start, Direction::SW))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Swap rows for columns, mirroring along the top-left to bottom-right
+    /// diagonal.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// Rotate a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let bottom = self.height as isize - 1;
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, bottom - p.x)].clone()
+        })
+    }
+
+    /// Rotate a quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let right = self.width as isize - 1;
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(right - p.y, p.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside the {width}x{height} grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s, Some)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    fn collect<'a>(cells: impl Iterator<Item = (Point, &'a char)>) -> String {
+        cells.map(|(_, c)| c).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        assert!(matches!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Grid::parse("..\n.x", |c| (c == '.').then_some(())),
+            Err(Error::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        assert_eq!(collect(grid.neighbours4(Point::new(0, 0))), "bd");
+        assert_eq!(collect(grid.neighbours8(Point::new(1, 1))), "bcfda");
+        assert_eq!(Point::new(0, 0).neighbours8().count(), 8);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.diagonals().map(collect).collect::<Vec<_>>(),
+            ["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(collect).collect::<Vec<_>>(),
+            ["a", "bd", "ce", "f"]
+        );
+        assert_eq!(collect(grid.ray(Point::new(2, 1), Direction::W)), "fed");
+
+        let empty = Grid::<char>::new(0, 2, vec![]);
+        assert_eq!(empty.columns().count(), 0);
+        assert_eq!(empty.rows().collect::<Vec<_>>(), [&[] as &[char]; 2]);
+        assert_eq!(empty.to_string(), "\n\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside a grid 3 wide")]
+    fn test_column_bounds() {
+        grid().column(3).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside a grid 2 high")]
+    fn test_row_bounds() {
+        grid().row(2);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::N.turn_cw(), Direction::E);
+        assert_eq!(Direction::NW.turn_cw(), Direction::NE);
+        assert_eq!(Direction::S.turn_ccw(), Direction::E);
+        assert_eq!(Direction::SW.opposite(), Direction::NE);
+        assert_eq!(Point::new(1, 1).step(Direction::NW), Point::new(0, 0));
+    }
+}
@@ -0,0 +1,587 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Sub},
+    slice,
+};
+
+/// What's needed of the values at the ends of an interval. Only differences
+/// between values that are in order are ever taken, so unsigned types are
+/// fine.
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_endpoint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The half-open interval `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    /// `[start, end)`, or an empty interval at `start` if `end` comes first.
+    pub fn new(start: T, end: T) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    /// `[start, start + len)`. Panics if the end overflows.
+    pub fn with_len(start: T, len: T) -> Self {
+        Self::checked_with_len(start, len).expect("interval end overflowed")
+    }
+
+    /// `[start, start + len)`, or `None` if the end overflows.
+    pub fn checked_with_len(start: T, len: T) -> Option<Self> {
+        Some(Self::new(start, start.checked_add(len)?))
+    }
+
+    pub fn len(&self) -> T {
+        self.end.max(self.start) - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The overlap of the two intervals, if there is any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of values stored as sorted intervals that are non-empty and don't
+/// overlap or touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn normalise(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// The intervals which overlap `interval`.
+    fn overlapping(&self, interval: Interval<T>) -> impl Iterator<Item = &Interval<T>> {
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        self.intervals[first..]
+            .iter()
+            .take_while(move |i| i.start < interval.end)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= x);
+        self.intervals.get(i).is_some_and(|i| i.contains(x))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.merge(&Self::from(interval));
+    }
+
+    /// Add every value in `other` to this set.
+    pub fn merge(&mut self, other: &Self) {
+        *self = self.union(other);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalise(self.iter().chain(other.iter()).copied().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let intervals = self
+            .iter()
+            .flat_map(|a| other.overlapping(*a).filter_map(|b| a.intersection(b)))
+            .collect();
+
+        // Pieces of disjoint intervals are still disjoint, but may now touch
+        Self::normalise(intervals)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+
+        for a in self.iter() {
+            let mut start = a.start;
+            for b in other.overlapping(*a) {
+                intervals.push(Interval::new(start, b.start));
+                start = b.end;
+            }
+            intervals.push(Interval::new(start, a.end));
+        }
+
+        Self::normalise(intervals)
+    }
+}
+
+impl<T: Endpoint> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::normalise(vec![interval])
+    }
+}
+
+impl<T: Endpoint> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::normalise(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+/// Maps every value in `src` onto the interval of the same length starting
+/// at `dst`, preserving order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    pub src: Interval<T>,
+    pub dst: T,
+}
+
+impl<T: Endpoint> Segment<T> {
+    pub fn new(src: T, dst: T, len: T) -> Self {
+        Self {
+            src: Interval::with_len(src, len),
+            dst,
+        }
+    }
+
+    pub fn convert(&self, x: T) -> Option<T> {
+        self.src
+            .contains(x)
+            .then(|| self.dst + (x - self.src.start))
+    }
+
+    /// Where the whole of `src` ends up.
+    pub fn image(&self) -> Interval<T> {
+        Interval::with_len(self.dst, self.src.len())
+    }
+
+    /// The part of this segment that maps from inside `src`.
+    pub fn restrict(&self, src: Interval<T>) -> Option<Self> {
+        let src = self.src.intersection(&src)?;
+        Some(Self {
+            src,
+            dst: self.dst + (src.start - self.src.start),
+        })
+    }
+
+    /// The part of this segment that maps to inside `dst`.
+    pub fn restrict_image(&self, dst: Interval<T>) -> Option<Self> {
+        let dst = self.image().intersection(&dst)?;
+        Some(Self {
+            src: Interval::with_len(self.src.start + (dst.start - self.dst), dst.len()),
+            dst: dst.start,
+        })
+    }
+
+    fn is_identity(&self) -> bool {
+        self.src.start == self.dst
+    }
+}
+
+impl<T: Endpoint + Display> Display for Segment<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.src, self.image())
+    }
+}
+
+/// A function made of [`Segment`]s, leaving any value outside of them
+/// unchanged. Segments are kept sorted and disjoint, with no identity
+/// segments and no neighbours that could be one segment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PiecewiseMap<T> {
+    segments: Vec<Segment<T>>,
+}
+
+impl<T> Default for PiecewiseMap<T> {
+    fn default() -> Self {
+        Self { segments: vec![] }
+    }
+}
+
+impl<T: Endpoint> PiecewiseMap<T> {
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// Build a map from segments which may overlap, in which case the first
+    /// segment covering a value is the one used for it.
+    pub fn new(segments: impl IntoIterator<Item = Segment<T>>) -> Self {
+        let mut covered = IntervalSet::new();
+        let mut pieces = vec![];
+
+        for segment in segments {
+            for free in IntervalSet::from(segment.src).difference(&covered).iter() {
+                pieces.extend(segment.restrict(*free));
+            }
+            covered.insert(segment.src);
+        }
+
+        Self::normalise(pieces)
+    }
+
+    /// Sort disjoint segments, dropping identities and joining neighbours.
+    fn normalise(mut pieces: Vec<Segment<T>>) -> Self {
+        pieces.retain(|s| !s.src.is_empty() && !s.is_identity());
+        pieces.sort_by_key(|s| s.src.start);
+
+        let mut segments: Vec<Segment<T>> = Vec::with_capacity(pieces.len());
+        for segment in pieces {
+            match segments.last_mut() {
+                Some(last)
+                    if last.src.end == segment.src.start && last.image().end == segment.dst =>
+                {
+                    last.src.end = segment.src.end
+                }
+                _ => segments.push(segment),
+            }
+        }
+
+        Self { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment<T>] {
+        &self.segments
+    }
+
+    /// Every value the segments map, i.e. everything that might change.
+    pub fn domain(&self) -> IntervalSet<T> {
+        self.segments.iter().map(|s| s.src).collect()
+    }
+
+    /// The segments which map values from inside `src`.
+    fn overlapping(&self, src: Interval<T>) -> impl Iterator<Item = &Segment<T>> {
+        let first = self.segments.partition_point(|s| s.src.end <= src.start);
+        self.segments[first..]
+            .iter()
+            .take_while(move |s| s.src.start < src.end)
+    }
+
+    pub fn convert(&self, x: T) -> T {
+        let i = self.segments.partition_point(|s| s.src.end <= x);
+        self.segments.get(i).and_then(|s| s.convert(x)).unwrap_or(x)
+    }
+
+    /// Where every value in `src` ends up.
+    pub fn convert_interval(&self, src: Interval<T>) -> IntervalSet<T> {
+        let mut out = vec![];
+        let mut start = src.start;
+
+        for segment in self.overlapping(src) {
+            out.push(Interval::new(start, segment.src.start));
+            out.extend(segment.restrict(src).map(|s| s.image()));
+            start = segment.src.end;
+        }
+        out.push(Interval::new(start, src.end));
+
+        out.into_iter().collect()
+    }
+
+    pub fn convert_set(&self, src: &IntervalSet<T>) -> IntervalSet<T> {
+        src.iter()
+            .flat_map(|i| self.convert_interval(*i).intervals.into_iter())
+            .collect()
+    }
+
+    /// Every value that ends up inside `dst`.
+    pub fn preimage_interval(&self, dst: Interval<T>) -> IntervalSet<T> {
+        // Segments are sorted by source, not image, so all need checking
+        let mut out: IntervalSet<T> = self
+            .segments
+            .iter()
+            .filter_map(|s| s.restrict_image(dst))
+            .map(|s| s.src)
+            .collect();
+        out.merge(&IntervalSet::from(dst).difference(&self.domain()));
+
+        out
+    }
+
+    pub fn preimage_set(&self, dst: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut out = IntervalSet::new();
+        for interval in dst {
+            out.merge(&self.preimage_interval(*interval));
+        }
+
+        out
+    }
+
+    /// The map that applies `self` and then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+
+        for segment in &self.segments {
+            let image = segment.image();
+
+            // Where `next` moves the output of this segment on again
+            for other in next.overlapping(image) {
+                let inner = segment.restrict_image(other.src).unwrap();
+                pieces.push(Segment {
+                    src: inner.src,
+                    dst: other.convert(inner.dst).unwrap(),
+                });
+            }
+
+            // and where it leaves it alone
+            for gap in IntervalSet::from(image).difference(&next.domain()).iter() {
+                pieces.extend(segment.restrict_image(*gap));
+            }
+        }
+
+        // Values that `self` leaves alone only go through `next`
+        for gap in next.domain().difference(&self.domain()).iter() {
+            pieces.extend(next.overlapping(*gap).filter_map(|s| s.restrict(*gap)));
+        }
+
+        Self::normalise(pieces)
+    }
+}
+
+/// One segment per line, in order.
+impl<T: Endpoint + Display> Display for PiecewiseMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            writeln!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Endpoint> FromIterator<Segment<T>> for PiecewiseMap<T> {
+    fn from_iter<I: IntoIterator<Item = Segment<T>>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(intervals: &[(u32, u32)]) -> IntervalSet<u32> {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(s, e))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::new(3u32, 7);
+        assert_eq!(i.len(), 4);
+        assert!(i.contains(3) && !i.contains(7));
+        assert_eq!(
+            i.intersection(&Interval::new(5, 9)),
+            Some(Interval::new(5, 7))
+        );
+        assert_eq!(i.intersection(&Interval::new(7, 9)), None);
+
+        let empty = Interval::new(5u32, 2);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+
+        assert_eq!(
+            Interval::checked_with_len(250u8, 5),
+            Some(Interval::new(250, 255))
+        );
+        assert_eq!(Interval::checked_with_len(251u8, 5), None);
+    }
+
+    #[test]
+    fn test_set_normalise() {
+        assert_eq!(
+            set(&[(5, 8), (0, 2), (2, 3), (7, 10), (20, 20)]).intervals(),
+            [Interval::new(0, 3), Interval::new(5, 10)]
+        );
+    }
+
+    #[test]
+    fn test_set_ops() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 40)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (28, 30)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 28)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20), (30, 40)]));
+        assert!(a.contains(0) && a.contains(29) && !a.contains(10) && !a.contains(30));
+        assert_eq!(a.min(), Some(0));
+        assert_eq!(IntervalSet::<u32>::new().min(), None);
+
+        let mut c = a.clone();
+        c.insert(Interval::new(10, 20));
+        assert_eq!(c, set(&[(0, 30)]));
+    }
+
+    #[test]
+    fn test_map() {
+        // The first map of the day 5 example
+        let map = PiecewiseMap::new([Segment::new(98u32, 50, 2), Segment::new(50, 52, 48)]);
+        assert_eq!(map.convert(0), 0);
+        assert_eq!(map.convert(50), 52);
+        assert_eq!(map.convert(97), 99);
+        assert_eq!(map.convert(99), 51);
+        assert_eq!(map.convert(100), 100);
+
+        assert_eq!(
+            map.convert_interval(Interval::new(45, 100)),
+            set(&[(45, 50), (50, 52), (52, 100)])
+        );
+        assert_eq!(
+            map.convert_set(&set(&[(96, 99)])),
+            set(&[(50, 51), (98, 100)])
+        );
+    }
+
+    #[test]
+    fn test_map_overlaps() {
+        let map = PiecewiseMap::new([Segment::new(10u32, 100, 10), Segment::new(5, 200, 20)]);
+        assert_eq!(map.convert(7), 202);
+        assert_eq!(map.convert(12), 102);
+        assert_eq!(map.convert(22), 217);
+        assert_eq!(map.segments().len(), 3);
+
+        // Neighbours with the same offset join, identities vanish
+        let map = PiecewiseMap::new([Segment::new(0u32, 10, 5), Segment::new(5, 15, 5)]);
+        assert_eq!(map.segments(), [Segment::new(0, 10, 10)]);
+        assert_eq!(
+            PiecewiseMap::new([Segment::new(3u32, 3, 5)]),
+            PiecewiseMap::identity()
+        );
+    }
+
+    #[test]
+    fn test_then() {
+        let a = PiecewiseMap::new([Segment::new(98u32, 50, 2), Segment::new(50, 52, 48)]);
+        let b = PiecewiseMap::new([
+            Segment::new(15u32, 0, 37),
+            Segment::new(52, 37, 2),
+            Segment::new(0, 39, 15),
+        ]);
+        let c = PiecewiseMap::new([Segment::new(40u32, 120, 30), Segment::new(130, 5, 3)]);
+
+        let ab = a.then(&b);
+        let abc = ab.then(&c);
+        for x in 0..200 {
+            assert_eq!(ab.convert(x), b.convert(a.convert(x)), "{x}");
+            assert_eq!(abc.convert(x), c.convert(b.convert(a.convert(x))), "{x}");
+        }
+
+        assert_eq!(PiecewiseMap::identity().then(&a), a);
+        assert_eq!(a.then(&PiecewiseMap::identity()), a);
+    }
+
+    #[test]
+    fn test_preimage() {
+        // Not one to one: 10..15 is reached from both 0..5 and itself
+        let map = PiecewiseMap::new([Segment::new(0u32, 10, 5), Segment::new(20, 0, 5)]);
+
+        for y in 0..40 {
+            let expected: IntervalSet<u32> = (0..40)
+                .filter(|&x| map.convert(x) == y)
+                .map(|x| Interval::with_len(x, 1))
+                .collect();
+            let found = map.preimage_interval(Interval::with_len(y, 1));
+            assert_eq!(
+                found.intersection(&Interval::new(0, 40).into()),
+                expected,
+                "{y}"
+            );
+        }
+
+        assert_eq!(
+            map.preimage_interval(Interval::new(3, 12)).intervals(),
+            [
+                Interval::new(0, 2),
+                Interval::new(5, 12),
+                Interval::new(23, 25)
+            ]
+        );
+        assert_eq!(
+            map.preimage_set(
+                &[Interval::new(0, 1), Interval::new(14, 16)]
+                    .into_iter()
+                    .collect()
+            )
+            .intervals(),
+            [
+                Interval::new(4, 5),
+                Interval::new(14, 16),
+                Interval::new(20, 21)
+            ]
+        );
+    }
+}
@@ -1,4 +1,10 @@
 pub mod aoc;
+pub mod bignum;
+pub mod days;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod poly;
 pub mod quant;
 
 pub struct IntoArrayChunks<I, const N: usize>
@@ -39,7 +45,7 @@ mod test {
 
     #[test]
     fn test_chunks() {
-        let v = vec![0, 1, 2, 3, 4];
+        let v = [0, 1, 2, 3, 4];
         let mut cs = arr_chunks(v.iter());
         assert_eq!(cs.next(), Some([&0, &1]));
         assert_eq!(cs.next(), Some([&2, &3]));
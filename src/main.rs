@@ -1,3 +1,202 @@
+use std::path::PathBuf;
+
+use aoc2023::{
+    aoc::{
+        answers::{KnownAnswers, Verdict},
+        bench::{self, Timings},
+        cli::InputDir,
+        error::read_input,
+        DynSolution, Part,
+    },
+    days,
+};
+use clap::{builder::RangedU64ValueParser, ArgGroup, CommandFactory, Parser};
+use colored::Colorize;
+
+#[derive(Debug, Clone, Copy)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+fn parse_selection(s: &str) -> Result<Selection, String> {
+    if s == "all" {
+        return Ok(Selection::All);
+    }
+
+    let day: u8 = s
+        .parse()
+        .map_err(|_| format!("expected a day number or `all`, got `{s}`"))?;
+
+    if days::get(day).is_some() {
+        Ok(Selection::Day(day))
+    } else {
+        Err(format!("day {day} has not been solved yet"))
+    }
+}
+
+/// Run Advent of Code 2023 solutions
+#[derive(Parser, Debug)]
+#[command(name = "aoc", group = ArgGroup::new("timing").args(["time", "bench"]))]
+struct Args {
+    /// Day to run, or `all` to run the whole calendar
+    #[arg(value_parser = parse_selection)]
+    day: Selection,
+
+    /// Only run this part
+    part: Option<Part>,
+
+    /// Input file for a single day, or `-` to read from stdin
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    #[command(flatten)]
+    input_dir: InputDir,
+
+    /// Compare answers against the known answers, failing on any mismatch
+    #[arg(long)]
+    check: bool,
+
+    /// File of known answers used by --check
+    #[arg(long, default_value = "answers/answers.toml")]
+    answers: PathBuf,
+
+    /// Report how long parsing and each part took
+    #[arg(long)]
+    time: bool,
+
+    /// Repeat every stage this many times and report min/median/mean times
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with = "time",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    bench: Option<usize>,
+
+    /// How to report times, as text alongside the answers or as a summary of
+    /// every day at the end. Needs --time or --bench
+    #[arg(long, value_enum, default_value_t = Format::Text, requires = "timing")]
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+impl Args {
+    /// How many times to run each stage, if timing at all.
+    fn runs(&self) -> Option<usize> {
+        self.bench.or(self.time.then_some(1))
+    }
+
+    /// Print a line of the per-day output, which gets out of the way of the
+    /// summary by going to stderr when one is being produced.
+    fn say(&self, line: String) {
+        match (self.format, self.runs()) {
+            (Format::Json | Format::Markdown, Some(_)) => eprintln!("{line}"),
+            _ => println!("{line}"),
+        }
+    }
+}
+
+/// Run one day, returning whether it produced its answers and, when checking,
+/// whether none of them were wrong. Timings are added to `report`.
+fn run(
+    args: &Args,
+    known: Option<&KnownAnswers>,
+    solution: &dyn DynSolution,
+    report: &mut Vec<(u8, Timings)>,
+) -> bool {
+    let day = solution.day();
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| args.input_dir.day_file(day));
+
+    args.say(format!("Day {day}"));
+    match read_input(&path)
+        .and_then(|input| solution.bench(&input, args.part, args.runs().unwrap_or(1)))
+    {
+        Ok((answers, timings)) => {
+            let mut ok = true;
+
+            for (part, answer) in answers.iter() {
+                match known {
+                    Some(known) => {
+                        let verdict = known.check(day, part, answer);
+                        ok &= !matches!(verdict, Verdict::Fail { .. });
+                        args.say(format!("  Part {part}: {answer} {verdict}"));
+                    }
+                    None => args.say(format!("  Part {part}: {answer}")),
+                }
+            }
+
+            if args.runs().is_some() {
+                if args.format == Format::Text {
+                    for (stage, stats) in timings.iter() {
+                        println!("  {} {stage}: {stats}", "Time".dimmed());
+                    }
+                }
+                report.push((day, timings));
+            }
+
+            ok
+        }
+        Err(e) => {
+            eprintln!("{}", e.render());
+            false
+        }
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let args = Args::parse();
+
+    let known = if args.check {
+        match KnownAnswers::load(&args.answers) {
+            Ok(known) => Some(known),
+            Err(e) => e.exit(),
+        }
+    } else {
+        None
+    };
+
+    let mut report = vec![];
+    let failures = match args.day {
+        Selection::All => {
+            if args.input.is_some() {
+                Args::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        "--input can only be used when running a single day",
+                    )
+                    .exit();
+            }
+
+            days::ALL
+                .iter()
+                .filter(|solution| !run(&args, known.as_ref(), **solution, &mut report))
+                .count()
+        }
+        Selection::Day(day) => usize::from(!run(
+            &args,
+            known.as_ref(),
+            days::get(day).unwrap(),
+            &mut report,
+        )),
+    };
+
+    match args.format {
+        Format::Text => {}
+        Format::Json => println!("{}", bench::to_json(&report)),
+        Format::Markdown => print!("{}", bench::to_markdown(&report)),
+    }
+
+    if failures > 0 {
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,196 @@
+//! Polynomials through evenly spaced samples, in Newton's forward difference
+//! form so that they can be read straight off a difference table.
+
+use std::fmt::{self, Display};
+
+use crate::quant::checked_gcd;
+
+/// The polynomial through samples taken at positions `0, 1, 2, ...`. Its
+/// value at `x` is the sum of `coefficients[k] * binomial(x, k)`, where
+/// `coefficients[k]` is the first value in the `k`th row of differences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Newton {
+    coefficients: Vec<i128>,
+    samples: usize,
+}
+
+/// What the difference table says about the samples it was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// A row of differences came out all zeros, so the samples follow a
+    /// polynomial of this degree. All zero samples count as degree 0.
+    Polynomial(usize),
+    /// Fewer than two samples, which leaves no differences to check.
+    TooShort,
+    /// The differences never reach a row of zeros. The fitted polynomial
+    /// still passes through every sample, but nothing backs up its guesses
+    /// beyond them.
+    NotPolynomial,
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shape::Polynomial(degree) => write!(f, "polynomial of degree {degree}"),
+            Shape::TooShort => write!(f, "too short to tell"),
+            Shape::NotPolynomial => write!(f, "not polynomial"),
+        }
+    }
+}
+
+impl Newton {
+    /// The polynomial of lowest degree through `samples`, or `None` if the
+    /// differences don't fit in an `i128`.
+    pub fn fit(samples: &[i128]) -> Option<Self> {
+        let mut row = samples.to_vec();
+        let mut coefficients = Vec::with_capacity(row.len());
+
+        // Each row of the table is worked out in place over the last
+        while let Some(&first) = row.first() {
+            coefficients.push(first);
+            for i in 0..row.len() - 1 {
+                row[i] = row[i + 1].checked_sub(row[i])?;
+            }
+            row.pop();
+        }
+
+        // Leading zeros don't change the polynomial, only its apparent degree
+        while coefficients.last() == Some(&0) {
+            coefficients.pop();
+        }
+
+        Some(Self {
+            coefficients,
+            samples: samples.len(),
+        })
+    }
+
+    /// How many samples the polynomial was fitted to.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Whether the samples really follow a polynomial. They do when the
+    /// table has a row past the last non-zero coefficient, since that row's
+    /// Newton coefficients, and so the whole row, are then zero.
+    pub fn shape(&self) -> Shape {
+        if self.samples < 2 {
+            Shape::TooShort
+        } else if self.coefficients.len() < self.samples {
+            Shape::Polynomial(self.degree().unwrap_or(0))
+        } else {
+            Shape::NotPolynomial
+        }
+    }
+
+    /// The first value of each row of differences, up to the last that isn't
+    /// zero.
+    pub fn coefficients(&self) -> &[i128] {
+        &self.coefficients
+    }
+
+    /// The degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// The value at `x`, which can be before the first sample or long after
+    /// the last, or `None` if it or any term of the sum doesn't fit in an
+    /// `i128`.
+    pub fn at(&self, x: i128) -> Option<i128> {
+        let mut value = 0i128;
+        let mut binomial = 1i128;
+
+        for (k, &coefficient) in self.coefficients.iter().enumerate() {
+            if k > 0 {
+                binomial = next_binomial(binomial, x, k as i128)?;
+            }
+            value = value.checked_add(coefficient.checked_mul(binomial)?)?;
+        }
+
+        Some(value)
+    }
+}
+
+/// `binomial(x, k)` from `binomial(x, k - 1)`, which is `previous * (x - k
+/// + 1) / k`. Any common factor of `k` and the new factor is taken out first,
+/// and what's left of `k` then divides `previous` exactly, so the product is
+/// never bigger than the result. Works for negative `x` too.
+fn next_binomial(previous: i128, x: i128, k: i128) -> Option<i128> {
+    let factor = x.checked_sub(k - 1)?;
+    if factor == 0 {
+        return Some(0);
+    }
+
+    let g = checked_gcd(factor, k)?;
+    (previous / (k / g)).checked_mul(factor / g)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cubic(x: i128) -> i128 {
+        2 * x * x * x - 5 * x * x + 7 * x - 11
+    }
+
+    #[test]
+    fn test_fit() {
+        let samples: Vec<_> = (0..8).map(cubic).collect();
+        let newton = Newton::fit(&samples).unwrap();
+        assert_eq!(newton.degree(), Some(3));
+        assert_eq!(newton.coefficients(), &[-11, 4, 2, 12]);
+
+        for x in -50..50 {
+            assert_eq!(newton.at(x), Some(cubic(x)), "{x}");
+        }
+
+        assert_eq!(Newton::fit(&[0, 0, 0]).unwrap().degree(), None);
+        assert_eq!(Newton::fit(&[]).unwrap().at(12), Some(0));
+        assert_eq!(Newton::fit(&[5, 5]).unwrap().degree(), Some(0));
+        assert_eq!(Newton::fit(&[i128::MIN, i128::MAX]), None);
+    }
+
+    #[test]
+    fn test_shape() {
+        let shape = |samples: &[i128]| Newton::fit(samples).unwrap().shape();
+        assert_eq!(shape(&[1, 3, 6, 10, 15, 21]), Shape::Polynomial(2));
+        assert_eq!(shape(&[4, 4]), Shape::Polynomial(0));
+        assert_eq!(shape(&[0, 0, 0]), Shape::Polynomial(0));
+        // Degree 2 needs a fourth value to show its differences settle
+        assert_eq!(shape(&[1, 3, 6]), Shape::NotPolynomial);
+        assert_eq!(shape(&[1, 2, 4, 8, 16, 32]), Shape::NotPolynomial);
+        assert_eq!(shape(&[7]), Shape::TooShort);
+        assert_eq!(shape(&[]), Shape::TooShort);
+    }
+
+    #[test]
+    fn test_far_positions() {
+        let newton = Newton::fit(&[0, 1, 4, 9]).unwrap();
+        let x = 1i128 << 62;
+        assert_eq!(newton.at(x), Some(x * x));
+        assert_eq!(newton.at(-x), Some(x * x));
+        assert_eq!(newton.at(1 << 64), None);
+
+        // binomial(x, 40) fits, though x^40 / 40! in any order wouldn't
+        let mut samples = vec![0; 41];
+        samples[40] = 1;
+        let newton = Newton::fit(&samples).unwrap();
+        assert_eq!(newton.degree(), Some(40));
+        assert_eq!(newton.at(100), Some(13_746_234_145_802_811_501_267_369_720));
+        assert_eq!(newton.at(-1), Some(1));
+        assert_eq!(newton.at(10_000), None);
+    }
+
+    #[test]
+    fn test_next_binomial() {
+        for x in -20i128..20 {
+            let mut binomial = 1;
+            for k in 1..15 {
+                binomial = next_binomial(binomial, x, k).unwrap();
+                let expected = (0..k).map(|i| x - i).product::<i128>() / (1..=k).product::<i128>();
+                assert_eq!(binomial, expected, "{x} {k}");
+            }
+        }
+    }
+}
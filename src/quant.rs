@@ -1,40 +1,306 @@
-use std::ops::{Div, Mul, Rem};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
-pub fn lcm<T>(a: T, b: T) -> T
+/// The least common multiple, which is never negative. Panics if it
+/// doesn't fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// The least common multiple, or `None` if it doesn't fit in `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    // Dividing first keeps the intermediate no bigger than the answer
+    let g = a.checked_gcd(b)?;
+    (a / g).checked_mul(b)?.checked_abs()
+}
+
+/// The greatest common divisor, which is never negative. Panics if it
+/// doesn't fit in `T`, which only happens for a signed `MIN` with itself or
+/// zero.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd overflowed")
+}
+
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    a.checked_gcd(b)
+}
+
+/// What [`gcd`] and [`lcm`] need, for every integer type.
+pub trait Integer: Copy + Eq + NumConsts + Div<Output = Self> + Mul<Output = Self> {
+    /// The gcd of the absolute values, if it fits in `Self`.
+    fn checked_gcd(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($typ:ty, $unsigned:ty, $abs:ident, $checked_abs:expr) => {
+        impl Integer for $typ {
+            fn checked_gcd(self, other: Self) -> Option<Self> {
+                // Stein's algorithm, on the magnitudes so signs can't upset it
+                let (mut a, mut b): ($unsigned, $unsigned) = (self.$abs(), other.$abs());
+                if a == 0 || b == 0 {
+                    return Self::try_from(a | b).ok();
+                }
+
+                let shift = (a | b).trailing_zeros();
+                a >>= a.trailing_zeros();
+                loop {
+                    b >>= b.trailing_zeros();
+                    if a > b {
+                        (a, b) = (b, a);
+                    }
+                    b -= a;
+                    if b == 0 {
+                        break;
+                    }
+                }
+
+                Self::try_from(a << shift).ok()
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$typ>::checked_mul(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                $checked_abs(self)
+            }
+        }
+    };
+    ($typ:ty) => {
+        impl_integer!($typ, $typ, clone, Some);
+    };
+    ($typ:ty, $unsigned:ty) => {
+        impl_integer!($typ, $unsigned, unsigned_abs, <$typ>::checked_abs);
+    };
+}
+impl_integer!(u8);
+impl_integer!(u16);
+impl_integer!(u32);
+impl_integer!(u64);
+impl_integer!(u128);
+impl_integer!(usize);
+impl_integer!(i8, u8);
+impl_integer!(i16, u16);
+impl_integer!(i32, u32);
+impl_integer!(i64, u64);
+impl_integer!(i128, u128);
+impl_integer!(isize, usize);
+
+/// The integer square root, the largest `r` with `r * r <= n`. A floating
+/// point estimate gets close, then correction steps make it exact.
+pub fn isqrt(n: u128) -> u128 {
+    let mut r = (n as f64).sqrt() as u128;
+
+    // r * r can overflow when the estimate overshoots near u128::MAX
+    while r.checked_mul(r).is_none_or(|square| square > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|square| square <= n) {
+        r += 1;
+    }
+
+    r
+}
+
+/// Extended Euclid: `(g, x, y)` with `a * x + b * y = g`, where `g` is the
+/// gcd of `a` and `b` and so never negative. Only signed types can hold the
+/// coefficients, which `Neg` enforces. Overflows in the same cases as
+/// [`gcd`].
+pub fn ext_gcd<T>(a: T, b: T) -> (T, T, T)
 where
-    T: Mul<Output = T> + Div<Output = T> + Eq + NumConsts + Rem<Output = T> + Copy,
+    T: Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + Ord
+        + NumConsts
+        + Copy,
 {
-    a * b / gcd(a, b)
+    let (mut g, mut g1) = (a, b);
+    let (mut x, mut x1) = (T::ONE, T::ZERO);
+    let (mut y, mut y1) = (T::ZERO, T::ONE);
+
+    while g1 != T::ZERO {
+        let q = g / g1;
+        (g, g1) = (g1, g - q * g1);
+        (x, x1) = (x1, x - q * x1);
+        (y, y1) = (y1, y - q * y1);
+    }
+
+    match g < T::ZERO {
+        true => (-g, -x, -y),
+        false => (g, x, y),
+    }
 }
 
-pub fn gcd<T>(a: T, b: T) -> T
-where
-    T: Rem<Output = T> + Eq + NumConsts + Copy,
+/// Arithmetic modulo a positive `m`, on values already reduced into `0..m`
+/// except where noted. Products never overflow: small types widen, and the
+/// 128 bit types fall back to adding and doubling.
+pub trait ModArith:
+    Integer + Ord + Add<Output = Self> + Sub<Output = Self> + Rem<Output = Self>
 {
-    if b == T::ZERO {
-        a
-    } else {
-        gcd(b, a % b)
+    /// Reduce any value, negative ones included, into `0..m`.
+    fn modulo(self, m: Self) -> Self;
+
+    fn mul_mod(self, other: Self, m: Self) -> Self;
+
+    fn is_odd(self) -> bool;
+
+    fn add_mod(self, other: Self, m: Self) -> Self {
+        if self >= m - other {
+            self - (m - other)
+        } else {
+            self + other
+        }
+    }
+
+    fn sub_mod(self, other: Self, m: Self) -> Self {
+        if self >= other {
+            self - other
+        } else {
+            m - (other - self)
+        }
     }
 }
 
+macro_rules! impl_mod_arith {
+    ($typ:ty, $wide:ty) => {
+        impl ModArith for $typ {
+            fn modulo(self, m: Self) -> Self {
+                self.rem_euclid(m)
+            }
+
+            fn mul_mod(self, other: Self, m: Self) -> Self {
+                ((self as $wide * other as $wide).rem_euclid(m as $wide)) as $typ
+            }
+
+            fn is_odd(self) -> bool {
+                self % 2 != 0
+            }
+        }
+    };
+    ($typ:ty) => {
+        impl ModArith for $typ {
+            fn modulo(self, m: Self) -> Self {
+                self.rem_euclid(m)
+            }
+
+            fn mul_mod(self, other: Self, m: Self) -> Self {
+                let (mut a, mut b, mut product) = (self, other, 0);
+                while b > 0 {
+                    if b % 2 != 0 {
+                        product = product.add_mod(a, m);
+                    }
+                    a = a.add_mod(a, m);
+                    b /= 2;
+                }
+                product
+            }
+
+            fn is_odd(self) -> bool {
+                self % 2 != 0
+            }
+        }
+    };
+}
+impl_mod_arith!(u8, u16);
+impl_mod_arith!(u16, u32);
+impl_mod_arith!(u32, u64);
+impl_mod_arith!(u64, u128);
+impl_mod_arith!(u128);
+impl_mod_arith!(usize, u128);
+impl_mod_arith!(i8, i16);
+impl_mod_arith!(i16, i32);
+impl_mod_arith!(i32, i64);
+impl_mod_arith!(i64, i128);
+impl_mod_arith!(i128);
+impl_mod_arith!(isize, i128);
+
+/// `base` to the power `exp`, modulo `m`. `exp` must not be negative.
+pub fn mod_pow<T: ModArith>(base: T, mut exp: T, m: T) -> T {
+    let mut base = base.modulo(m);
+    let mut result = T::ONE.modulo(m);
+
+    while exp > T::ZERO {
+        if exp.is_odd() {
+            result = result.mul_mod(base, m);
+        }
+        base = base.mul_mod(base, m);
+        exp = exp / (T::ONE + T::ONE);
+    }
+
+    result
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: ModArith>(a: T, m: T) -> Option<T> {
+    // Euclid, keeping the coefficient of `a` reduced so it never goes
+    // negative or overflows
+    let (mut g, mut g1) = (m, a.modulo(m));
+    let (mut x, mut x1) = (T::ZERO, T::ONE.modulo(m));
+
+    while g1 != T::ZERO {
+        let q = g / g1;
+        (g, g1) = (g1, g - q * g1);
+        (x, x1) = (x1, x.sub_mod(q.modulo(m).mul_mod(x1, m), m));
+    }
+
+    (g == T::ONE).then_some(x)
+}
+
+/// Solve every congruence `x = a (mod m)` in `congruences` at once, for
+/// moduli which needn't be coprime. The answer is the smallest solution and
+/// the modulus every solution is congruent under, or `None` if the
+/// congruences contradict each other or that modulus doesn't fit in `T`.
+pub fn crt<T: ModArith>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::ZERO, T::ONE), |(a, m), (b, n)| {
+            let g = gcd(m, n);
+            let diff = b.modulo(n).sub_mod(a.modulo(n), n);
+            if diff % g != T::ZERO {
+                return None;
+            }
+
+            // a + m * k solves both when m / g * k = diff / g (mod n / g)
+            let (m_g, n_g) = (m / g, n / g);
+            let k = (diff / g).mul_mod(mod_inverse(m_g, n_g)?, n_g);
+            let lcm = m_g.checked_mul(n)?;
+
+            // a < m and m * k <= lcm - m, so neither step overflows
+            Some((a.modulo(m) + m * k, lcm))
+        })
+}
+
 pub trait QuantIter: Iterator {
     fn lcm(mut self) -> Option<Self::Item>
     where
-        Self::Item: Mul<Output = Self::Item>
-            + Div<Output = Self::Item>
-            + Eq
-            + NumConsts
-            + Rem<Output = Self::Item>
-            + Copy,
+        Self::Item: Integer,
         Self: Sized,
     {
         self.next().map(|first| self.fold(first, lcm))
     }
 
+    /// The lcm of every item, or `None` if there are none or it overflows.
+    fn checked_lcm(mut self) -> Option<Self::Item>
+    where
+        Self::Item: Integer,
+        Self: Sized,
+    {
+        let first = self.next()?.checked_abs()?;
+        self.try_fold(first, checked_lcm)
+    }
+
     fn gcd(mut self) -> Option<Self::Item>
     where
-        Self::Item: Rem<Output = Self::Item> + Eq + NumConsts + Copy,
+        Self::Item: Integer,
         Self: Sized,
     {
         self.next().map(|first| self.fold(first, gcd))
@@ -75,3 +341,149 @@ impl_consts!(i128);
 impl_consts!(isize);
 impl_consts!(f32, f);
 impl_consts!(f64, f);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(48u32, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd(0u8, 9), 9);
+        assert_eq!(gcd(-48i32, 18), 6);
+        assert_eq!(gcd(48i32, -18), 6);
+        assert_eq!(gcd(-7i8, 0), 7);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(u128::MAX, u128::MAX - 1), 1);
+        assert_eq!(checked_gcd(i32::MIN, 0), None);
+        assert_eq!(checked_gcd(i32::MIN, i32::MIN), None);
+
+        for a in -50i32..50 {
+            for b in -50i32..50 {
+                let expected = (1..=50).rev().find(|d| a % d == 0 && b % d == 0);
+                let expected = if a == 0 && b == 0 {
+                    0
+                } else {
+                    expected.unwrap()
+                };
+                assert_eq!(gcd(a, b), expected, "{a} {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4u32, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(-4i32, -6), 12);
+        assert_eq!(lcm(0u8, 5), 0);
+        // a * b would overflow before dividing
+        assert_eq!(lcm(200u8, 100), 200);
+        assert_eq!(lcm(u64::MAX, u64::MAX), u64::MAX);
+
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(checked_lcm(i8::MIN, 1), None);
+        assert_eq!(checked_lcm(i8::MIN, 2), None);
+        assert_eq!(checked_lcm(-64i8, 2), Some(64));
+    }
+
+    #[test]
+    fn test_quant_iter() {
+        assert_eq!([4u32, 6, 10].into_iter().lcm(), Some(60));
+        assert_eq!([12i32, -18, 30].into_iter().gcd(), Some(6));
+        assert_eq!(std::iter::empty::<u8>().lcm(), None);
+
+        assert_eq!([16u8, 12, 10].into_iter().checked_lcm(), Some(240));
+        assert_eq!([16u8, 12, 10, 3].into_iter().checked_lcm(), Some(240));
+        assert_eq!([16u8, 12, 10, 7].into_iter().checked_lcm(), None);
+        assert_eq!([-5i8].into_iter().checked_lcm(), Some(5));
+        assert_eq!(std::iter::empty::<u8>().checked_lcm(), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u128 {
+            assert_eq!(isqrt(n), n.isqrt(), "{n}");
+        }
+
+        // Around squares big enough that f64 can't tell neighbours apart
+        for r in [
+            1u128 << 40,
+            (1 << 53) + 1,
+            u64::MAX as u128,
+            u64::MAX as u128 - 12345,
+        ] {
+            assert_eq!(isqrt(r * r), r, "{r}");
+            assert_eq!(isqrt(r * r - 1), r - 1, "{r}");
+            assert_eq!(isqrt(r * r + 1), r, "{r}");
+        }
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for (a, b) in [
+            (240i64, 46),
+            (46, 240),
+            (-12, 18),
+            (12, -18),
+            (-12, -18),
+            (17, 5),
+            (0, 7),
+            (7, 0),
+            (0, -7),
+            (-7, 0),
+            (0, 0),
+        ] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(a * x + b * y, g, "{a} {b}");
+            assert!(g >= 0, "{a} {b}");
+            assert_eq!(g, gcd(a, b), "{a} {b}");
+        }
+        assert_eq!(ext_gcd(12i64, -18), (6, -1, -1));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4u32, 13, 497), 445);
+        assert_eq!(mod_pow(-4i32, 3, 7), 6);
+        assert_eq!(mod_pow(5u8, 0, 1), 0);
+        assert_eq!(mod_pow(200u8, 77, 251), 160);
+
+        // Fermat, with products that would overflow without widening
+        let p = 18_446_744_073_709_551_557u64;
+        assert_eq!(mod_pow(123_456_789, p - 1, p), 1);
+        let p = 170_141_183_460_469_231_731_687_303_715_884_105_727i128;
+        assert_eq!(mod_pow(3, p - 1, p), 1);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3u32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6u32, 9), None);
+        assert_eq!(mod_inverse(5u8, 1), Some(0));
+
+        let p = u64::MAX - 58;
+        let inverse = mod_inverse(u64::MAX - 1, p).unwrap();
+        assert_eq!((u64::MAX - 1).mul_mod(inverse, p), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2u32, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0usize, 2), (1, 3)]), Some((4, 6)));
+        // Moduli with common factors
+        assert_eq!(crt([(2i64, 4), (0, 6)]), Some((6, 12)));
+        assert_eq!(crt([(1i64, 4), (0, 6)]), None);
+        assert_eq!(crt([(-1i32, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt::<u8>([]), Some((0, 1)));
+        // The combined modulus doesn't fit
+        assert_eq!(crt([(1u8, 16), (2, 17)]), None);
+
+        let big = crt([(1u128, u64::MAX as u128), (2, u64::MAX as u128 - 1)]).unwrap();
+        assert_eq!(big.0 % (u64::MAX as u128), 1);
+        assert_eq!(big.0 % (u64::MAX as u128 - 1), 2);
+    }
+}